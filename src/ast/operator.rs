@@ -1,19 +1,40 @@
+pub mod table;
+
+use crate::hir::HirIdentifier;
 use crate::hir::ir::{expr::*, path::HirPath};
+use crate::span::Span;
+use self::table::*;
 
 pub type OperationParserResult<T> = Result<T, OperationParserError>;
 
+// Token index points to the offending token in the input sequence.
 #[derive(Clone, Debug, PartialEq)]
 pub enum OperationParserError {
-    InvalidKindOfTerm,
-    InvalidLengthOfTerm,
+    InvalidKindOfTerm { token_index: usize },
+    InvalidLengthOfTerm { token_index: usize },
+    NonAssociativeChain { token_index: usize },
+    UnbalancedGroup { token_index: usize },
+}
+
+impl OperationParserError {
+    pub fn token_index(&self) -> usize {
+        match self {
+            OperationParserError::InvalidKindOfTerm { token_index }
+                | OperationParserError::InvalidLengthOfTerm { token_index }
+                | OperationParserError::NonAssociativeChain { token_index }
+                | OperationParserError::UnbalancedGroup { token_index } => *token_index,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum OperatorPrecedenceMode {
-    InputPrecedence,
-    StackPrecedence,
+enum PostfixNotationAction {
+    PushToStack,
+    PopToOutput,
+    PopBothToOutput,
 }
 
+// Index of the token in the input sequence.
 struct IndexedToken<T>(usize, T);
 
 impl<T> IndexedToken<T> {
@@ -36,18 +57,36 @@ impl OperationParser {
     // 入力のtopとスタックのtopの優先度を比較する
     // 　入力側の優先度が高い：入力→スタック送り
     // 　スタック側の優先度が高い：スタック→出力送り
-    // 　優先度が同じ：入力・スタックどちらも出力送り
+    // 　優先度が同じ：入力側の演算子の結合性で判断する
+    // 　　左結合：スタック→出力送り
+    // 　　右結合：入力→スタック送り
+    // 　　非結合：NonAssociativeChainエラー
+    // 　グループの開始と終了が対応した場合：入力・スタックどちらも出力送り
     //   ※数値/IDの場合は必ずスタック送りなので入力→スタック送りして最適化
     // ※前置/中置/後置で重複した記号の演算子に注意（事前に演算子の位置を判断して分類する）
     // 比較が不可能なケースのエラーも実装
     pub fn parse(input: HirOperationSequence) -> OperationParserResult<HirExpression> {
-        let output = OperationParser::into_postfix_notation(input)?;
-        OperationParser::construct_expression(output)
+        let output = OperationParser::into_indexed_postfix_notation(OperationParser::index_tokens(input))?;
+        OperationParser::construct_indexed_expression(output)
+    }
+
+    pub fn into_postfix_notation(input: HirOperationSequence) -> OperationParserResult<HirOperationSequence> {
+        let output = OperationParser::into_indexed_postfix_notation(OperationParser::index_tokens(input))?;
+        Ok(output.into_iter().map(|v| v.value()).collect())
+    }
+
+    pub fn construct_expression(input: HirOperationSequence) -> OperationParserResult<HirExpression> {
+        OperationParser::construct_indexed_expression(OperationParser::index_tokens(input))
+    }
+
+    fn index_tokens(input: HirOperationSequence) -> Vec<IndexedToken<HirOperationToken>> {
+        input.into_iter().enumerate().map(|(i, v)| IndexedToken::new(i, v)).collect()
     }
 
-    pub fn into_postfix_notation(mut input: HirOperationSequence) -> OperationParserResult<HirOperationSequence> {
+    // Tokens keep their indexes in the input sequence to report the location of errors.
+    fn into_indexed_postfix_notation(mut input: Vec<IndexedToken<HirOperationToken>>) -> OperationParserResult<Vec<IndexedToken<HirOperationToken>>> {
         input.reverse();
-        let mut stack = Vec::new();
+        let mut stack: Vec<IndexedToken<HirOperationToken>> = Vec::new();
         let mut output = Vec::new();
 
         loop {
@@ -59,159 +98,177 @@ impl OperationParser {
                 break Ok(output);
             }
 
-            let input_precedence = match next_input {
-                Some(v) => match v {
-                    HirOperationToken::Operator(operator) => OperationParser::get_operator_precedence(operator, OperatorPrecedenceMode::InputPrecedence) + 1,
-                    HirOperationToken::Term(_) => usize::MAX,
+            match OperationParser::compare_tokens(next_input, next_stack)? {
+                PostfixNotationAction::PushToStack => stack.push(input.pop().unwrap()),
+                PostfixNotationAction::PopToOutput => output.push(stack.pop().unwrap()),
+                PostfixNotationAction::PopBothToOutput => {
+                    output.push(stack.pop().unwrap());
+                    output.push(input.pop().unwrap());
                 },
-                None => 0,
-            };
+            }
+        }
+    }
 
-            let stack_precedence = match next_stack {
-                Some(v) => match v {
-                    HirOperationToken::Operator(operator) => OperationParser::get_operator_precedence(operator, OperatorPrecedenceMode::StackPrecedence) + 1,
-                    HirOperationToken::Term(_) => usize::MAX,
-                },
-                None => 0,
-            };
+    fn compare_tokens(input: Option<&IndexedToken<HirOperationToken>>, stack: Option<&IndexedToken<HirOperationToken>>) -> OperationParserResult<PostfixNotationAction> {
+        let (input_index, input_operator, stack_index, stack_operator) = match (input, stack) {
+            (None, Some(IndexedToken(index, HirOperationToken::Operator(HirOperator::GroupBegin)))) => return Err(OperationParserError::UnbalancedGroup { token_index: *index }),
+            (None, _) => return Ok(PostfixNotationAction::PopToOutput),
+            (Some(IndexedToken(index, HirOperationToken::Operator(HirOperator::GroupEnd))), None) => return Err(OperationParserError::UnbalancedGroup { token_index: *index }),
+            (Some(_), None) => return Ok(PostfixNotationAction::PushToStack),
+            // Terms never stay in the stack.
+            (Some(_), Some(IndexedToken(_, HirOperationToken::Term(_)))) => return Ok(PostfixNotationAction::PopToOutput),
+            (Some(IndexedToken(_, HirOperationToken::Term(_))), Some(_)) => return Ok(PostfixNotationAction::PushToStack),
+            (
+                Some(IndexedToken(input_index, HirOperationToken::Operator(input_operator))),
+                Some(IndexedToken(stack_index, HirOperationToken::Operator(stack_operator))),
+            ) => (*input_index, input_operator, *stack_index, stack_operator),
+        };
 
-            if input_precedence < stack_precedence {
-                output.push(stack.pop().unwrap());
-            } else if stack_precedence < input_precedence {
-                stack.push(input.pop().unwrap());
-            } else {
-                output.push(stack.pop().unwrap());
-                output.push(input.pop().unwrap());
+        match (input_operator, stack_operator) {
+            (HirOperator::GroupEnd, HirOperator::GroupBegin) => return Ok(PostfixNotationAction::PopBothToOutput),
+            (HirOperator::GroupEnd, _) => return Ok(PostfixNotationAction::PopToOutput),
+            (HirOperator::GroupBegin, _) | (_, HirOperator::GroupBegin) => return Ok(PostfixNotationAction::PushToStack),
+            (_, HirOperator::GroupEnd) => return Err(OperationParserError::UnbalancedGroup { token_index: stack_index }),
+            _ => (),
+        }
+
+        let input_precedence = OperationParser::get_operator_precedence(input_operator);
+        let stack_precedence = OperationParser::get_operator_precedence(stack_operator);
+
+        if input_precedence > stack_precedence {
+            Ok(PostfixNotationAction::PushToStack)
+        } else if input_precedence < stack_precedence {
+            Ok(PostfixNotationAction::PopToOutput)
+        } else {
+            match OperationParser::get_operator_associativity(input_operator) {
+                OperatorAssociativity::Left => Ok(PostfixNotationAction::PopToOutput),
+                OperatorAssociativity::Right => Ok(PostfixNotationAction::PushToStack),
+                OperatorAssociativity::None => Err(OperationParserError::NonAssociativeChain { token_index: input_index }),
             }
         }
     }
 
-    pub fn get_operator_precedence(operator: &HirOperator, mode: OperatorPrecedenceMode) -> usize {
-        let is_input_mode = mode == OperatorPrecedenceMode::InputPrecedence;
-        let is_stack_mode = mode == OperatorPrecedenceMode::StackPrecedence;
+    // Group operators are not compared by precedence.
+    pub fn get_operator_precedence(operator: &HirOperator) -> usize {
+        match operator {
+            HirOperator::GroupBegin | HirOperator::GroupEnd => 0,
+            _ => OperationParser::get_operator_definition(operator).precedence,
+        }
+    }
 
+    pub fn get_operator_associativity(operator: &HirOperator) -> OperatorAssociativity {
         match operator {
-            HirOperator::Substitute if is_input_mode => 4,
-            HirOperator::Substitute if is_stack_mode => 3,
-            HirOperator::Add if is_input_mode => 5,
-            HirOperator::Add if is_stack_mode => 6,
-            HirOperator::Subtract if is_input_mode => 5,
-            HirOperator::Subtract if is_stack_mode => 6,
-            HirOperator::Multiply if is_input_mode => 7,
-            HirOperator::Multiply if is_stack_mode => 8,
-            HirOperator::Negative if is_input_mode => 10,
-            HirOperator::Negative if is_stack_mode => 9,
-            HirOperator::Not if is_input_mode => 10,
-            HirOperator::Not if is_stack_mode => 9,
-            HirOperator::Nonnize if is_input_mode => 11,
-            HirOperator::Nonnize if is_stack_mode => 12,
-            HirOperator::Propagate if is_input_mode => 11,
-            HirOperator::Propagate if is_stack_mode => 12,
-            HirOperator::FunctionCall(_) if is_input_mode => 11,
-            HirOperator::FunctionCall(_) if is_stack_mode => 12,
-            HirOperator::MemberAccess if is_input_mode => 13,
-            HirOperator::MemberAccess if is_stack_mode => 14,
-            HirOperator::Path if is_input_mode => 15,
-            HirOperator::Path if is_stack_mode => 16,
-            HirOperator::GroupBegin if is_input_mode => 17,
-            HirOperator::GroupBegin if is_stack_mode => 1,
-            HirOperator::GroupEnd if is_input_mode => 1,
-            HirOperator::GroupEnd if is_stack_mode => unreachable!(),
-            _ => unimplemented!(),
+            HirOperator::GroupBegin | HirOperator::GroupEnd => OperatorAssociativity::None,
+            _ => OperationParser::get_operator_definition(operator).associativity,
         }
     }
 
-    pub fn construct_expression(input: HirOperationSequence) -> OperationParserResult<HirExpression> {
+    fn get_operator_definition(operator: &HirOperator) -> &'static OperatorDefinition {
+        match OperatorDefinition::find(operator) {
+            Some(v) => v,
+            None => unreachable!("operator is not defined in operator table"),
+        }
+    }
+
+    // Left and right terms are distinguished by their indexes in the input sequence.
+    fn construct_indexed_expression(input: Vec<IndexedToken<HirOperationToken>>) -> OperationParserResult<HirExpression> {
         let mut stack: Vec<IndexedToken<HirExpression>> = Vec::new();
+        let last_token_index = input.iter().map(|v| v.index()).max().unwrap_or(0);
 
-        let pop_term = |stack: &mut Vec<IndexedToken<HirExpression>>| match stack.pop() {
+        let pop_term = |token_index: usize, stack: &mut Vec<IndexedToken<HirExpression>>| match stack.pop() {
             Some(v) => Ok(v),
-            None => Err(OperationParserError::InvalidLengthOfTerm),
+            None => Err(OperationParserError::InvalidLengthOfTerm { token_index }),
         };
 
-        let pop_two_terms = |operator_index: i32, stack: &mut Vec<IndexedToken<HirExpression>>| {
-            let term1 = pop_term(stack)?;
-            let term2 = pop_term(stack)?;
+        let pop_two_terms = |token_index: usize, stack: &mut Vec<IndexedToken<HirExpression>>| {
+            let term1 = pop_term(token_index, stack)?;
+            let term2 = pop_term(token_index, stack)?;
 
             let indexed_terms = if term1.index() < term2.index() {
-                (operator_index as usize, term1.value(), term2.value())
+                (token_index, term1.value(), term2.value())
             } else {
-                (operator_index as usize, term2.value(), term1.value())
+                (token_index, term2.value(), term1.value())
             };
 
             Ok(indexed_terms)
         };
 
-        let mut token_index = -1i32;
-
         for each_token in input {
-            token_index += 1;
+            let token_index = each_token.index();
 
-            let (output_token_index, operation) = match each_token {
+            let (output_token_index, operation) = match each_token.value() {
                 HirOperationToken::Operator(operator) => match operator {
-                    HirOperator::Substitute => {
-                        let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
-                        (index, HirOperation::Substitute(left, right))
-                    },
-                    HirOperator::Add => {
-                        let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
-                        (index, HirOperation::Add(left, right))
-                    },
-                    HirOperator::Subtract => {
-                        let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
-                        (index, HirOperation::Subtract(left, right))
-                    },
-                    HirOperator::Multiply => {
-                        let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
-                        (index, HirOperation::Multiply(left, right))
-                    },
-                    HirOperator::Not => (token_index as usize, HirOperation::Not(pop_term(&mut stack)?.value())),
-                    HirOperator::BitNot => (token_index as usize, HirOperation::BitNot(pop_term(&mut stack)?.value())),
-                    HirOperator::Negative => (token_index as usize, HirOperation::Negative(pop_term(&mut stack)?.value())),
-                    HirOperator::Nonnize => (token_index as usize, HirOperation::Nonnize(pop_term(&mut stack)?.value())),
-                    HirOperator::Propagate => (token_index as usize, HirOperation::Propagate(pop_term(&mut stack)?.value())),
-                    HirOperator::FunctionCall(arguments) => (token_index as usize, HirOperation::FunctionCall(pop_term(&mut stack)?.value(), arguments)),
-                    HirOperator::MemberAccess => {
-                        let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
-                        (index, HirOperation::MemberAccess(left, right))
-                    },
+                    HirOperator::FunctionCall(arguments) => (token_index, HirOperation::FunctionCall(pop_term(token_index, &mut stack)?.value(), arguments)),
                     HirOperator::Path => {
                         let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
 
                         let mut segments =
-                            if let HirExpression::Identifier(v) = left {
+                            if let HirExpressionKind::Identifier(v) = left.kind {
                                 vec![v]
-                            } else if let HirExpression::Operation(v) = left {
+                            } else if let HirExpressionKind::Literal(HirLiteral::SelfValue) = left.kind {
+                                // `self` is parsed as a literal but refers to the current module in paths.
+                                vec![HirIdentifier::new("self".to_string(), left.span)]
+                            } else if let HirExpressionKind::Operation(v) = left.kind {
                                 if let HirOperation::Path(HirPath::Unresolved(v)) = *v {
                                     v
                                 } else {
-                                    return Err(OperationParserError::InvalidKindOfTerm);
+                                    return Err(OperationParserError::InvalidKindOfTerm { token_index });
                                 }
                             } else {
-                                return Err(OperationParserError::InvalidKindOfTerm);
+                                return Err(OperationParserError::InvalidKindOfTerm { token_index });
                             };
 
-                        if let HirExpression::Identifier(v) = right {
+                        if let HirExpressionKind::Identifier(v) = right.kind {
                             segments.push(v);
                         } else {
-                            return Err(OperationParserError::InvalidKindOfTerm);
+                            return Err(OperationParserError::InvalidKindOfTerm { token_index });
                         }
 
                         (index, HirOperation::Path(HirPath::Unresolved(segments)))
                     },
-                    HirOperator::GroupBegin => (token_index as usize, HirOperation::Group(pop_term(&mut stack)?.value())),
+                    HirOperator::GroupBegin => (token_index, HirOperation::Group(pop_term(token_index, &mut stack)?.value())),
                     HirOperator::GroupEnd => continue,
+                    _ => match OperationParser::get_operator_definition(&operator).constructor {
+                        HirOperationConstructor::Unary(constructor) => (token_index, constructor(pop_term(token_index, &mut stack)?.value())),
+                        HirOperationConstructor::Binary(constructor) => {
+                            let (index, left, right) = pop_two_terms(token_index, &mut stack)?;
+                            (index, constructor(left, right))
+                        },
+                        HirOperationConstructor::Custom => unreachable!("operator with custom constructor must be handled individually"),
+                    },
                 },
                 HirOperationToken::Term(term) => {
-                    stack.push(IndexedToken::new(token_index as usize, term));
+                    stack.push(IndexedToken::new(token_index, term));
                     continue;
                 },
             };
 
-            let new_operation = HirExpression::Operation(Box::new(operation));
+            // Operator tokens have no span, so the operation covers its terms.
+            let span = OperationParser::join_term_spans(&operation);
+            let new_operation = HirExpression::new(HirExpressionKind::Operation(Box::new(operation)), span);
             stack.push(IndexedToken(output_token_index, new_operation));
         }
 
-        Ok(pop_term(&mut stack)?.value())
+        let expression = pop_term(last_token_index, &mut stack)?;
+
+        // Terms which are not consumed by any operator.
+        if let Some(term) = stack.pop() {
+            return Err(OperationParserError::InvalidLengthOfTerm { token_index: term.index() });
+        }
+
+        Ok(expression.value())
+    }
+
+    fn join_term_spans(operation: &HirOperation) -> Span {
+        match operation {
+            HirOperation::Path(HirPath::Unresolved(segments)) => segments.iter()
+                .map(|v| v.span().clone())
+                .reduce(|joined, each_span| joined.join(&each_span))
+                .unwrap_or_default(),
+            _ => operation.terms().iter()
+                .map(|v| v.span.clone())
+                .reduce(|joined, each_span| joined.join(&each_span))
+                .unwrap_or_default(),
+        }
     }
 }
@@ -0,0 +1,137 @@
+use std::mem;
+use crate::hir::ir::expr::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OperatorFixity {
+    Prefix,
+    Infix,
+    Postfix,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OperatorAssociativity {
+    Left,
+    Right,
+    None,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum HirOperationConstructor {
+    Unary(fn(HirExpression) -> HirOperation),
+    Binary(fn(HirExpression, HirExpression) -> HirOperation),
+    // Constructed by OperationParser itself because the operator has a special form of terms.
+    Custom,
+}
+
+#[derive(Debug)]
+pub struct OperatorDefinition {
+    // None when the operator is not written as a fixed symbol like function call.
+    pub symbol: Option<&'static str>,
+    pub fixity: OperatorFixity,
+    // Higher value binds tighter.
+    pub precedence: usize,
+    pub associativity: OperatorAssociativity,
+    pub operator: HirOperator,
+    pub constructor: HirOperationConstructor,
+    // None when the operator needs special lowering in JS.
+    pub js_symbol: Option<&'static str>,
+    // Precedence of the JS operator which differs from Karin's. Higher value binds tighter.
+    // JS associativity is the same as the one above except that comparisons are left-associative in JS.
+    pub js_precedence: Option<usize>,
+}
+
+impl OperatorDefinition {
+    pub fn find(operator: &HirOperator) -> Option<&'static OperatorDefinition> {
+        OPERATORS.iter().find(|v| mem::discriminant(&v.operator) == mem::discriminant(operator))
+    }
+
+    pub fn find_by_symbol(symbol: &str, fixity: OperatorFixity) -> Option<&'static OperatorDefinition> {
+        OPERATORS.iter().find(|v| v.symbol == Some(symbol) && v.fixity == fixity)
+    }
+
+    pub fn find_by_js_symbol(js_symbol: &str, fixity: OperatorFixity) -> Option<&'static OperatorDefinition> {
+        OPERATORS.iter().find(|v| v.js_symbol == Some(js_symbol) && v.fixity == fixity)
+    }
+
+    // Longer symbols precede the shorter ones which share the same prefix.
+    pub fn symbols(fixity: OperatorFixity) -> Vec<&'static str> {
+        let mut symbols: Vec<&'static str> = OPERATORS.iter()
+            .filter(|v| v.fixity == fixity)
+            .filter_map(|v| v.symbol)
+            .collect();
+
+        symbols.sort_by(|a, b| b.len().cmp(&a.len()));
+        symbols
+    }
+}
+
+macro_rules! operator {
+    ($symbol:expr, $fixity:ident, $precedence:expr, $associativity:ident, $operator:ident, $constructor:ident, $js_symbol:expr, $js_precedence:expr) => {
+        OperatorDefinition {
+            symbol: $symbol,
+            fixity: OperatorFixity::$fixity,
+            precedence: $precedence,
+            associativity: OperatorAssociativity::$associativity,
+            operator: HirOperator::$operator,
+            constructor: HirOperationConstructor::$constructor(HirOperation::$operator),
+            js_symbol: $js_symbol,
+            js_precedence: $js_precedence,
+        }
+    };
+}
+
+// Group operators are not listed because they are not compared by precedence.
+pub static OPERATORS: &[OperatorDefinition] = &[
+    operator!(Some("="), Infix, 1, Right, Substitute, Binary, Some("="), Some(2)),
+    operator!(Some("+="), Infix, 1, Right, AddSubstitute, Binary, Some("+="), Some(2)),
+    operator!(Some("-="), Infix, 1, Right, SubtractSubstitute, Binary, Some("-="), Some(2)),
+    operator!(Some("*="), Infix, 1, Right, MultiplySubstitute, Binary, Some("*="), Some(2)),
+    operator!(Some("/="), Infix, 1, Right, DivideSubstitute, Binary, Some("/="), Some(2)),
+    operator!(Some("%="), Infix, 1, Right, RemainderSubstitute, Binary, Some("%="), Some(2)),
+    operator!(Some("||"), Infix, 2, Left, Or, Binary, Some("||"), Some(3)),
+    operator!(Some("&&"), Infix, 3, Left, And, Binary, Some("&&"), Some(4)),
+    // Chained comparisons like `a < b < c` are rejected.
+    operator!(Some("=="), Infix, 4, None, Equal, Binary, Some("==="), Some(8)),
+    operator!(Some("!="), Infix, 4, None, NotEqual, Binary, Some("!=="), Some(8)),
+    operator!(Some("<"), Infix, 4, None, LessThan, Binary, Some("<"), Some(9)),
+    operator!(Some("<="), Infix, 4, None, LessThanOrEqual, Binary, Some("<="), Some(9)),
+    operator!(Some(">"), Infix, 4, None, GreaterThan, Binary, Some(">"), Some(9)),
+    operator!(Some(">="), Infix, 4, None, GreaterThanOrEqual, Binary, Some(">="), Some(9)),
+    operator!(Some("|"), Infix, 5, Left, BitOr, Binary, Some("|"), Some(5)),
+    operator!(Some("^"), Infix, 6, Left, BitXor, Binary, Some("^"), Some(6)),
+    operator!(Some("&"), Infix, 7, Left, BitAnd, Binary, Some("&"), Some(7)),
+    operator!(Some("<<"), Infix, 8, Left, LeftShift, Binary, Some("<<"), Some(10)),
+    operator!(Some(">>"), Infix, 8, Left, RightShift, Binary, Some(">>"), Some(10)),
+    operator!(Some("+"), Infix, 9, Left, Add, Binary, Some("+"), Some(11)),
+    operator!(Some("-"), Infix, 9, Left, Subtract, Binary, Some("-"), Some(11)),
+    operator!(Some("*"), Infix, 10, Left, Multiply, Binary, Some("*"), Some(12)),
+    operator!(Some("/"), Infix, 10, Left, Divide, Binary, Some("/"), Some(12)),
+    operator!(Some("%"), Infix, 10, Left, Remainder, Binary, Some("%"), Some(12)),
+    // Prefix operators are applied from the nearest one to the term.
+    operator!(Some("-"), Prefix, 11, Right, Negative, Unary, Some("-"), Some(14)),
+    operator!(Some("!"), Prefix, 11, Right, Not, Unary, Some("!"), Some(14)),
+    operator!(Some("~"), Prefix, 11, Right, BitNot, Unary, Some("~"), Some(14)),
+    operator!(Some("!"), Postfix, 12, Left, Nonnize, Unary, None, None),
+    operator!(Some("?"), Postfix, 12, Left, Propagate, Unary, None, None),
+    OperatorDefinition {
+        symbol: None,
+        fixity: OperatorFixity::Postfix,
+        precedence: 12,
+        associativity: OperatorAssociativity::Left,
+        operator: HirOperator::FunctionCall(Vec::new()),
+        constructor: HirOperationConstructor::Custom,
+        js_symbol: None,
+        js_precedence: Some(17),
+    },
+    operator!(Some("."), Infix, 13, Left, MemberAccess, Binary, Some("."), Some(17)),
+    OperatorDefinition {
+        symbol: Some("::"),
+        fixity: OperatorFixity::Infix,
+        precedence: 14,
+        associativity: OperatorAssociativity::Left,
+        operator: HirOperator::Path,
+        constructor: HirOperationConstructor::Custom,
+        js_symbol: None,
+        js_precedence: None,
+    },
+];
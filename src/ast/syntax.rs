@@ -1,10 +1,16 @@
 pub mod expr;
 pub mod item;
 
-use volt::{*, element::*};
+use std::cell::RefCell;
+use volt::{*, element::*, rule::RuleId, tree::*};
 use volt_derive::VoltModuleDefinition;
 use self::{item::*, expr::{*, Expression}};
 
+thread_local! {
+    // Building the grammar is expensive, so that identifier checks share one on each thread.
+    static IDENTIFIER_VOLT: RefCell<Volt> = RefCell::new(Syntax::generate_volt(1024));
+}
+
 pub struct Syntax;
 
 impl Syntax {
@@ -23,8 +29,38 @@ impl Syntax {
         volt.add_module(DataType::new());
         volt
     }
+
+    // Whether the id can be referred by paths like hako and module names.
+    pub fn is_identifier(id: &str) -> bool {
+        IDENTIFIER_VOLT.with(|v| v.borrow_mut().parse(id, &RuleId("Identifier::identifier".to_string())).is_ok())
+    }
+
+    pub fn is_error_node(node: &SyntaxNode) -> bool {
+        SYNTAX_ERROR_NAMES.contains(&node.name.as_str())
+    }
+
+    // Collects error nodes in source order. Children of an error node are not searched.
+    pub fn collect_errors(node: &SyntaxNode) -> Vec<&SyntaxNode> {
+        if Syntax::is_error_node(node) {
+            return vec![node];
+        }
+
+        node.children.filter_nodes().into_iter().flat_map(|v| Syntax::collect_errors(v)).collect()
+    }
 }
 
+// Names of error nodes produced by recovery points and reducers.
+pub const SYNTAX_ERROR_NAMES: &[&str] = &[
+    "invalid_item",
+    "invalid_function_body",
+    "digit_separator_on_side",
+    "starts_with_zero",
+    "has_capital_letter",
+    "explicit_plus_symbol",
+    "ends_with_zero",
+    "stray_doc_comment",
+];
+
 const WHITESPACE: fn() -> Element = || Symbol::whitespace().min(0).hide();
 const WHITESPACE_REQUIRED: fn() -> Element = || Symbol::whitespace().min(1).hide();
 
@@ -33,14 +69,27 @@ struct Main {
     main: Element,
     item: Element,
     accessibility: Element,
+    item_recovery: Element,
 }
 
 impl VoltModule for Main {
     fn new() -> Main {
         define_rules!{
-            main := choice![Item::item().separate_around(WHITESPACE()), WHITESPACE()];
+            main := seq![
+                // Byte order mark which some editors on Windows insert.
+                str("\u{FEFF}").optional().hide(),
+                choice![Item::item().separate_around(WHITESPACE()), WHITESPACE()],
+            ];
             item := choice![Function::function()];
             accessibility := choice![str("pub@hako"), str("pub")].optional();
+            // Skips to the next line which starts an item.
+            item_recovery := seq![
+                chars(r"\S"),
+                seq![
+                    seq![Symbol::newline(), Symbol::whitespace().min(0), choice![str("fn"), str("use"), str("pub")]].neglook(),
+                    chars(r"\s\S"),
+                ].min(0),
+            ].join().reduce(|children| vec![SyntaxChild::error("invalid_item".to_string(), children)]);
         }
     }
 }
@@ -50,14 +99,44 @@ struct Symbol {
     whitespace: Element,
     expression_separator: Element,
     around_expression_separator: Element,
+    line_comment: Element,
+    block_comment: Element,
+    doc_comment: Element,
+    newline: Element,
+    unicode_whitespace: Element,
 }
 
 impl VoltModule for Symbol {
     fn new() -> Symbol {
         define_rules!{
-            whitespace := choice![str(" "), str("\t"), str("\n")];
-            expression_separator := choice![str("\n"), str(";")].around(Symbol::around_expression_separator().min(0));
-            around_expression_separator := choice![str(" "), str("\t")];
+            whitespace := choice![str(" "), str("\t"), Symbol::newline(), Symbol::unicode_whitespace(), Symbol::line_comment(), Symbol::block_comment()];
+            expression_separator := choice![Symbol::newline(), str(";")].around(Symbol::around_expression_separator().min(0));
+            // Line comment does not consume the newline to separate expressions.
+            around_expression_separator := choice![str(" "), str("\t"), Symbol::unicode_whitespace(), Symbol::line_comment(), Symbol::block_comment()];
+            // CRLF is accepted for files edited on Windows.
+            newline := choice![str("\r\n"), str("\n")];
+            // Space separators like no-break space and ideographic space.
+            unicode_whitespace := chars(r"\p{Zs}");
+            // "////" is a line comment as well as "//".
+            line_comment := seq![
+                str("//"),
+                choice![str("/").neglook(), str("//")],
+                seq![Symbol::newline().neglook(), chars(r"\s\S")].min(0),
+            ];
+            // Block comments can be nested like "/* /* */ */".
+            block_comment := seq![
+                str("/*"),
+                choice![
+                    Symbol::block_comment(),
+                    seq![str("*/").neglook(), chars(r"\s\S")],
+                ].min(0),
+                str("*/"),
+            ];
+            doc_comment := seq![
+                str("///"),
+                str("/").neglook(),
+                seq![Symbol::newline().neglook(), chars(r"\s\S")].min(0),
+            ].join();
         }
     }
 }
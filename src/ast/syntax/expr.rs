@@ -1,11 +1,13 @@
 use volt::{*, element::*, tree::*};
 use volt_derive::VoltModuleDefinition;
+use crate::ast::operator::table::{OperatorDefinition, OperatorFixity};
 use super::*;
 
 #[derive(VoltModuleDefinition)]
 pub(super) struct Expression {
     expression: Element,
     pure_expression: Element,
+    path_root: Element,
 }
 
 impl VoltModule for Expression {
@@ -36,7 +38,10 @@ impl VoltModule for Expression {
                 Literal::literal(),
                 DataType::data_type(),
                 Identifier::identifier(),
+                Expression::path_root(),
             ];
+            // Reserved keyword which starts a path like `hako::a`.
+            path_root := str("hako");
         }
     }
 }
@@ -63,13 +68,15 @@ impl VoltModule for Literal {
             let leaf = children.get_leaf(0);
             let pure_value = leaf.value.replace('_', "");
             let new_leaf = SyntaxChild::leaf(leaf.start.clone(), pure_value.clone());
+            // Error nodes keep the source text to suggest fixes.
+            let source_leaf = SyntaxChild::leaf(leaf.start.clone(), leaf.value.clone());
             let mut errors = Vec::new();
 
             if leaf.value.starts_with('_') || leaf.value.ends_with('_') {
                 errors.push(
                     SyntaxChild::error(
                         "digit_separator_on_side".to_string(),
-                        vec![new_leaf.clone()],
+                        vec![source_leaf.clone()],
                     ),
                 );
             }
@@ -78,7 +85,7 @@ impl VoltModule for Literal {
                 errors.push(
                     SyntaxChild::error(
                         "starts_with_zero".to_string(),
-                        vec![new_leaf.clone()],
+                        vec![source_leaf.clone()],
                     ),
                 );
             }
@@ -88,7 +95,7 @@ impl VoltModule for Literal {
                     errors.push(
                         SyntaxChild::error(
                             "has_capital_letter".to_string(),
-                            vec![new_leaf.clone()],
+                            vec![source_leaf.clone()],
                         ),
                     );
 
@@ -107,13 +114,15 @@ impl VoltModule for Literal {
             let leaf = children.get_leaf(0);
             let pure_value = leaf.value.replace('_', "");
             let new_leaf = SyntaxChild::leaf(leaf.start.clone(), pure_value.clone());
+            // Error nodes keep the source text to suggest fixes.
+            let source_leaf = SyntaxChild::leaf(leaf.start.clone(), leaf.value.clone());
             let mut errors = Vec::new();
 
             if leaf.value.starts_with('_') || leaf.value.ends_with('_') {
                 errors.push(
                     SyntaxChild::error(
                         "digit_separator_on_side".to_string(),
-                        vec![new_leaf.clone()],
+                        vec![source_leaf.clone()],
                     ),
                 );
             }
@@ -122,7 +131,7 @@ impl VoltModule for Literal {
                 errors.push(
                     SyntaxChild::error(
                         "ends_with_zero".to_string(),
-                        vec![new_leaf.clone()],
+                        vec![source_leaf.clone()],
                     ),
                 );
             }
@@ -200,6 +209,13 @@ impl VoltModule for Literal {
     }
 }
 
+// Generates the choice of operator symbols from the operator table.
+fn operator_symbols(fixity: OperatorFixity) -> Element {
+    let mut symbols = OperatorDefinition::symbols(fixity).into_iter().map(|v| str(v));
+    let first_symbol = symbols.next().expect("operator table has no symbol of the fixity");
+    symbols.fold(first_symbol, |choice, symbol| choice![choice, symbol])
+}
+
 #[derive(VoltModuleDefinition)]
 pub(super) struct Operation {
     operation: Element,
@@ -245,9 +261,7 @@ impl VoltModule for Operation {
                 WHITESPACE(),
                 str(")").group("operator"),
             ];
-            prefix_operator := choice![
-                str("!"), str("~"), str("-"),
-            ].reduce(|mut v| match v.pop().unwrap() {
+            prefix_operator := operator_symbols(OperatorFixity::Prefix).reduce(|mut v| match v.pop().unwrap() {
                 SyntaxChild::Leaf(mut leaf) => {
                     leaf.set_value(format!("{}e", leaf.value));
                     vec![SyntaxChild::Leaf(leaf)]
@@ -255,8 +269,10 @@ impl VoltModule for Operation {
                 _ => unreachable!(),
             });
             postfix_operator := choice![
-                choice![
-                    str("!"), str("?"),
+                seq![
+                    // Not to consume the first character of infix operators like "!=".
+                    Operation::infix_operator().neglook(),
+                    operator_symbols(OperatorFixity::Postfix),
                 ].reduce(|mut v| match v.pop().unwrap() {
                     SyntaxChild::Leaf(mut leaf) => {
                         leaf.set_value(format!("e{}", leaf.value));
@@ -273,9 +289,7 @@ impl VoltModule for Operation {
                 WHITESPACE(),
                 str(")").hide(),
             ];
-            infix_operator := choice![
-                str("="), str("+"), str("-"), str("*"), str("."), str("::"),
-            ];
+            infix_operator := operator_symbols(OperatorFixity::Infix);
         }
     }
 }
@@ -1,4 +1,4 @@
-use volt::{*, element::*};
+use volt::{*, element::*, tree::*};
 use volt_derive::VoltModuleDefinition;
 
 use super::{*, expr::Expression};
@@ -6,12 +6,24 @@ use super::{*, expr::Expression};
 #[derive(VoltModuleDefinition)]
 pub(super) struct Item {
     item: Element,
+    documentation: Element,
+    stray_documentation: Element,
 }
 
 impl VoltModule for Item {
     fn new() -> Item {
         define_rules!{
-            item := choice![UseDeclaration::use_declaration(), Function::function()];
+            item := choice![
+                seq![
+                    seq![Item::documentation(), WHITESPACE()].optional(),
+                    choice![UseDeclaration::use_declaration(), Function::function(), Main::item_recovery().expand_once()],
+                ],
+                Item::stray_documentation().expand_once(),
+            ];
+            documentation := Symbol::doc_comment().expand_once().separate(WHITESPACE());
+            // Doc comments which document nothing like the ones at the end of file or in function body.
+            stray_documentation := Symbol::doc_comment().expand_once().separate(WHITESPACE())
+                .reduce(|children| vec![SyntaxChild::error("stray_doc_comment".to_string(), children)]);
         }
     }
 }
@@ -19,19 +31,36 @@ impl VoltModule for Item {
 #[derive(VoltModuleDefinition)]
 pub(super) struct UseDeclaration {
     use_declaration: Element,
+    use_tree: Element,
+    group: Element,
+    glob: Element,
+    alias: Element,
 }
 
 impl VoltModule for UseDeclaration {
     fn new() -> UseDeclaration {
         define_rules!{
-            use_declaration := seq![
-                str("use").hide(), WHITESPACE_REQUIRED(),
+            use_declaration := seq![str("use").hide(), WHITESPACE_REQUIRED(), UseDeclaration::use_tree().expand_once()];
+            // Path is followed by a group, a glob or an alias.
+            use_tree := seq![
                 choice![
                     str("hako"),
                     str("self"),
+                    str("super"),
                     Identifier::identifier().expand_once(),
                 ].separate(seq![WHITESPACE(), str("::").hide(), WHITESPACE()]),
+                choice![
+                    seq![WHITESPACE(), str("::").hide(), WHITESPACE(), choice![UseDeclaration::group(), UseDeclaration::glob()]],
+                    UseDeclaration::alias(),
+                ].optional(),
             ];
+            group := seq![
+                str("{").hide(), WHITESPACE(),
+                UseDeclaration::use_tree().separate(str(",").separate_around(WHITESPACE()).hide()),
+                WHITESPACE(), str("}").hide(),
+            ];
+            glob := str("*").hide();
+            alias := seq![WHITESPACE_REQUIRED(), str("as").hide(), WHITESPACE_REQUIRED(), Identifier::identifier().expand_once()];
         }
     }
 }
@@ -40,6 +69,7 @@ impl VoltModule for UseDeclaration {
 pub(super) struct Function {
     function: Element,
     formal_argument: Element,
+    body_recovery: Element,
 }
 
 impl VoltModule for Function {
@@ -54,8 +84,17 @@ impl VoltModule for Function {
                 str(")").hide(), WHITESPACE(),
                 DataType::data_type().optional(), WHITESPACE(),
                 str("{").hide(), WHITESPACE(),
-                Expression::expression().separate_around(Symbol::expression_separator().min(0).hide()).optional().group("exprs"), WHITESPACE(),
-                str("}").hide(),
+                choice![
+                    seq![
+                        choice![Expression::expression(), Item::stray_documentation().expand_once()]
+                            .separate_around(Symbol::expression_separator().min(0).hide()).optional().group("exprs"), WHITESPACE(),
+                        str("}").hide(),
+                    ],
+                    seq![
+                        Function::body_recovery().expand_once().group("exprs"),
+                        str("}").hide(),
+                    ],
+                ],
             ];
             formal_argument := seq![
                 seq![str("mut"), WHITESPACE_REQUIRED()].optional(),
@@ -68,6 +107,9 @@ impl VoltModule for Function {
                     ],
                 ],
             ];
+            // Skips to the closing brace of the function body.
+            body_recovery := seq![str("}").neglook(), chars(r"\s\S")].min(1).join()
+                .reduce(|children| vec![SyntaxChild::error("invalid_function_body".to_string(), children)]);
         }
     }
 }
@@ -0,0 +1,9 @@
+use std::{env, io, process};
+use compiler::cli::Karinc;
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let (mut stdout, mut stderr) = (io::stdout(), io::stderr());
+    let status = Karinc::new(&mut stdout, &mut stderr).run(&arguments);
+    process::exit(status);
+}
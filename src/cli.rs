@@ -0,0 +1,395 @@
+use std::{env, fmt, fs, io::{self, IsTerminal, Write}, path::{Path, PathBuf}};
+use crate::{Compiler, diagnostic::{Diagnostic, code::DiagnosticCode, json::DiagnosticJsonWriter, message::DiagnosticLanguage, render::DiagnosticRenderer}};
+use crate::hako::loader::{HakoLoader, HakoLoaderError};
+use crate::hako::graph::HakoGraph;
+use crate::hir::hirify::AstHako;
+use crate::hako::manifest::MANIFEST_FILE_NAME;
+use crate::js::{JsTranspiler, JsTranspilerError, JsTranspilerOptions};
+use crate::span::SourceMap;
+
+pub const EXIT_SUCCESS: i32 = 0;
+// At least one error is reported in the input.
+pub const EXIT_COMPILE_ERROR: i32 = 1;
+// Arguments are invalid or files cannot be read or written.
+pub const EXIT_USAGE_ERROR: i32 = 2;
+
+pub const USAGE: &str = "\
+Usage: karinc <command> [options] <input>...
+       karinc --explain <code>
+
+Commands:
+    build       Compile inputs and write JavaScript files
+    check       Report errors without writing any file
+    emit-ast    Print the syntax tree
+    emit-hir    Print the HIR
+    emit-js     Print the generated JavaScript
+
+Inputs are source files, hako directories or `hako.toml` manifests.
+Each of them is compiled into one output.
+
+Options:
+    -o, --output <path>          Output file, or output directory for multiple inputs
+        --error-format <format>  human (default) or json
+        --color <when>           auto (default), always or never
+        --checked-arithmetic     Throw RangeError on integer division by zero
+        --explain <code>         Explain the error code like K0001 in English
+    -h, --help                   Print this message
+    -V, --version                Print the version
+
+Exit status:
+    0    Compiled successfully
+    1    Errors are reported in the input
+    2    Invalid arguments or I/O failure
+";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KarincSubcommand {
+    Build,
+    Check,
+    EmitAst,
+    EmitHir,
+    EmitJs,
+}
+
+impl KarincSubcommand {
+    pub fn from_name(name: &str) -> Option<KarincSubcommand> {
+        let subcommand = match name {
+            "build" => KarincSubcommand::Build,
+            "check" => KarincSubcommand::Check,
+            "emit-ast" => KarincSubcommand::EmitAst,
+            "emit-hir" => KarincSubcommand::EmitHir,
+            "emit-js" => KarincSubcommand::EmitJs,
+            _ => return None,
+        };
+
+        Some(subcommand)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum KarincErrorFormat {
+    #[default]
+    Human,
+    // One JSON document per input file, separated by newlines.
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum KarincColor {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KarincOptions {
+    pub subcommand: KarincSubcommand,
+    pub inputs: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub error_format: KarincErrorFormat,
+    pub color: KarincColor,
+    pub checked_arithmetic: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KarincCommand {
+    Compile(KarincOptions),
+    Explain(String),
+    Help,
+    Version,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KarincArgumentError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidValue { option: String, value: String },
+    MissingInput,
+}
+
+impl fmt::Display for KarincArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KarincArgumentError::MissingCommand => write!(f, "no command is specified"),
+            KarincArgumentError::UnknownCommand(v) => write!(f, "unknown command `{}`", v),
+            KarincArgumentError::UnknownOption(v) => write!(f, "unknown option `{}`", v),
+            KarincArgumentError::MissingValue(v) => write!(f, "option `{}` requires a value", v),
+            KarincArgumentError::InvalidValue { option, value } => write!(f, "invalid value `{}` for option `{}`", value, option),
+            KarincArgumentError::MissingInput => write!(f, "no input is specified"),
+        }
+    }
+}
+
+impl KarincCommand {
+    // Arguments do not include the program name.
+    pub fn parse(arguments: &[String]) -> Result<KarincCommand, KarincArgumentError> {
+        let mut subcommand = None;
+        let mut inputs = Vec::new();
+        let mut output = None;
+        let mut error_format = KarincErrorFormat::default();
+        let mut color = KarincColor::default();
+        let mut checked_arithmetic = false;
+        let mut rest = arguments.iter();
+
+        while let Some(each_argument) = rest.next() {
+            // Accepts both of "--option value" and "--option=value".
+            let (option, inline_value) = match each_argument.split_once('=') {
+                Some((option, value)) if each_argument.starts_with("--") => (option, Some(value.to_string())),
+                _ => (each_argument.as_str(), None),
+            };
+
+            let mut value = || match inline_value.clone().or_else(|| rest.next().cloned()) {
+                Some(v) => Ok(v),
+                None => Err(KarincArgumentError::MissingValue(option.to_string())),
+            };
+
+            match option {
+                "-h" | "--help" => return Ok(KarincCommand::Help),
+                "-V" | "--version" => return Ok(KarincCommand::Version),
+                "--explain" => return Ok(KarincCommand::Explain(value()?)),
+                "-o" | "--output" => output = Some(PathBuf::from(value()?)),
+                "--error-format" => error_format = match value()?.as_str() {
+                    "human" => KarincErrorFormat::Human,
+                    "json" => KarincErrorFormat::Json,
+                    v => return Err(KarincArgumentError::InvalidValue { option: option.to_string(), value: v.to_string() }),
+                },
+                "--color" => color = match value()?.as_str() {
+                    "auto" => KarincColor::Auto,
+                    "always" => KarincColor::Always,
+                    "never" => KarincColor::Never,
+                    v => return Err(KarincArgumentError::InvalidValue { option: option.to_string(), value: v.to_string() }),
+                },
+                "--checked-arithmetic" => checked_arithmetic = true,
+                _ if option.starts_with('-') => return Err(KarincArgumentError::UnknownOption(option.to_string())),
+                _ => match subcommand {
+                    None => match KarincSubcommand::from_name(option) {
+                        Some(v) => subcommand = Some(v),
+                        None => return Err(KarincArgumentError::UnknownCommand(option.to_string())),
+                    },
+                    Some(_) => inputs.push(PathBuf::from(option)),
+                },
+            }
+        }
+
+        let subcommand = match subcommand {
+            Some(v) => v,
+            None => return Err(KarincArgumentError::MissingCommand),
+        };
+
+        if inputs.len() == 0 {
+            return Err(KarincArgumentError::MissingInput);
+        }
+
+        Ok(KarincCommand::Compile(KarincOptions { subcommand, inputs, output, error_format, color, checked_arithmetic }))
+    }
+}
+
+// Source file and the path of its output relative to the output directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KarincInput {
+    pub path: PathBuf,
+    pub relative_output: PathBuf,
+}
+
+pub struct Karinc<'a> {
+    stdout: &'a mut dyn Write,
+    stderr: &'a mut dyn Write,
+    language: DiagnosticLanguage,
+}
+
+impl<'a> Karinc<'a> {
+    pub fn new(stdout: &'a mut dyn Write, stderr: &'a mut dyn Write) -> Karinc<'a> {
+        Karinc { stdout, stderr, language: DiagnosticLanguage::from_env() }
+    }
+
+    pub fn with_language(mut self, language: DiagnosticLanguage) -> Karinc<'a> {
+        self.language = language;
+        self
+    }
+
+    // Returns the exit status.
+    pub fn run(&mut self, arguments: &[String]) -> i32 {
+        let command = match KarincCommand::parse(arguments) {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = writeln!(self.stderr, "error: {}\n\n{}", e, USAGE);
+                return EXIT_USAGE_ERROR;
+            },
+        };
+
+        let result = match command {
+            KarincCommand::Help => write!(self.stdout, "{}", USAGE).map(|_| EXIT_SUCCESS),
+            KarincCommand::Version => writeln!(self.stdout, "karinc {}", env!("CARGO_PKG_VERSION")).map(|_| EXIT_SUCCESS),
+            KarincCommand::Explain(code) => match DiagnosticCode::explain(&code) {
+                Some(explanation) => write!(self.stdout, "{}", explanation).map(|_| EXIT_SUCCESS),
+                None => writeln!(self.stderr, "error: unknown error code `{}`", code).map(|_| EXIT_USAGE_ERROR),
+            },
+            KarincCommand::Compile(options) => self.compile(&options),
+        };
+
+        match result {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = writeln!(self.stderr, "error: {}", e);
+                EXIT_USAGE_ERROR
+            },
+        }
+    }
+
+    pub fn compile(&mut self, options: &KarincOptions) -> io::Result<i32> {
+        let inputs = Karinc::collect_inputs(&options.inputs);
+        let mut has_error = false;
+
+        for each_input in &inputs {
+            has_error |= self.compile_input(options, each_input, inputs.len() > 1)?;
+        }
+
+        Ok(if has_error { EXIT_COMPILE_ERROR } else { EXIT_SUCCESS })
+    }
+
+    // Returns whether any error is reported.
+    fn compile_input(&mut self, options: &KarincOptions, input: &KarincInput, multiple: bool) -> io::Result<bool> {
+        if !input.path.exists() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("failed to read `{}`: no such file or directory", input.path.display())));
+        }
+
+        let mut source_map = SourceMap::new();
+        let id = HakoLoader::id_from_path(&input.path);
+        let mut transpiler_options = JsTranspilerOptions { checked_arithmetic: options.checked_arithmetic };
+
+        let (hakos, loader_errors) = if input.path.join(MANIFEST_FILE_NAME).is_file() {
+            match HakoGraph::load(&input.path, &mut source_map) {
+                Ok(graph) => {
+                    // Options in the root manifest are enabled in addition to the command-line options.
+                    transpiler_options.checked_arithmetic |= graph.nodes[0].manifest.options.checked_arithmetic;
+                    HakoLoader::load_graph(&mut source_map, &graph)
+                },
+                Err(e) => {
+                    let diagnostics = e.into_iter().map(|v| JsTranspilerError::LoaderError(HakoLoaderError::Graph(v)).into()).collect();
+                    self.report(options, &source_map, diagnostics)?;
+                    return Ok(true);
+                },
+            }
+        } else if input.path.is_dir() {
+            let (hako, errors) = HakoLoader::new(&mut source_map).load(&id, &input.path);
+            (vec![hako], errors)
+        } else {
+            let (hako, errors) = HakoLoader::new(&mut source_map).load_file(&id, &input.path);
+            (vec![hako], errors)
+        };
+
+        let transpiler = JsTranspiler::new(transpiler_options);
+        let mut errors: Vec<JsTranspilerError> = loader_errors.into_iter().map(|v| JsTranspilerError::LoaderError(v)).collect();
+
+        if options.subcommand == KarincSubcommand::EmitAst {
+            self.report(options, &source_map, errors.iter().map(|v| v.clone().into()).collect())?;
+            let trees: Vec<String> = hakos.iter().flat_map(|v| &v.modules).map(|v| format!("{:#?}\n", v.tree.root)).collect();
+            self.emit(options, input, multiple, &trees.concat())?;
+            return Ok(errors.len() != 0);
+        }
+
+        let asts: Vec<AstHako> = hakos.iter().map(|v| v.ast()).collect();
+        let (hir, analyzer_errors, warnings) = transpiler.analyze_hakos(asts.iter().collect());
+        errors.extend(analyzer_errors);
+
+        let mut diagnostics: Vec<Diagnostic> = errors.iter().map(|v| v.clone().into()).collect();
+        diagnostics.extend(warnings.into_iter().map(|v| v.into()));
+        Diagnostic::mark_conflicting_suggestions(&mut diagnostics);
+        self.report(options, &source_map, diagnostics)?;
+
+        match options.subcommand {
+            // HIR is printed with error placeholders to debug the analysis.
+            KarincSubcommand::EmitHir => self.emit(options, input, multiple, &format!("{:#?}\n", hir))?,
+            _ if errors.len() != 0 => (),
+            KarincSubcommand::EmitJs => self.emit(options, input, multiple, &transpiler.generate(&hir))?,
+            KarincSubcommand::Build => {
+                let output_path = Karinc::output_path(options, input, multiple);
+                Karinc::write_file(&output_path, &transpiler.generate(&hir))?;
+            },
+            _ => (),
+        }
+
+        Ok(errors.len() != 0)
+    }
+
+    // Warnings are reported without failing the compilation.
+    fn report(&mut self, options: &KarincOptions, source_map: &SourceMap, diagnostics: Vec<Diagnostic>) -> io::Result<()> {
+        if diagnostics.len() == 0 {
+            return Ok(());
+        }
+
+        match options.error_format {
+            KarincErrorFormat::Human => {
+                let colored = match options.color {
+                    KarincColor::Auto => io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none(),
+                    KarincColor::Always => true,
+                    KarincColor::Never => false,
+                };
+
+                // The colored crate disables itself by some environment variables.
+                if colored {
+                    colored::control::set_override(true);
+                }
+
+                let renderer = DiagnosticRenderer::new(source_map, colored).with_language(self.language);
+                write!(self.stderr, "{}", renderer.render_all(&diagnostics))
+            },
+            KarincErrorFormat::Json => {
+                // Written to stderr not to mix with emitted code on stdout.
+                let value = DiagnosticJsonWriter::new(source_map).with_language(self.language).value(&diagnostics);
+                writeln!(self.stderr, "{}", value)
+            },
+        }
+    }
+
+    // Emitted text goes to stdout unless the output path is specified.
+    fn emit(&mut self, options: &KarincOptions, input: &KarincInput, multiple: bool, text: &str) -> io::Result<()> {
+        match &options.output {
+            Some(_) => Karinc::write_file(&Karinc::output_path(options, input, multiple), text),
+            None => write!(self.stdout, "{}", text),
+        }
+    }
+
+    // Output is located next to the source when the output path is not specified.
+    pub fn output_path(options: &KarincOptions, input: &KarincInput, multiple: bool) -> PathBuf {
+        let extension = match options.subcommand {
+            KarincSubcommand::EmitAst => "ast",
+            KarincSubcommand::EmitHir => "hir",
+            _ => "js",
+        };
+
+        match &options.output {
+            Some(v) if !multiple && !v.is_dir() => v.clone(),
+            Some(v) => v.join(&input.relative_output).with_extension(extension),
+            None => input.path.with_extension(extension),
+        }
+    }
+
+    // Manifest file is replaced with the hako directory which contains it.
+    pub fn collect_inputs(paths: &[PathBuf]) -> Vec<KarincInput> {
+        paths.iter().map(|v| match v.parent() {
+            // Canonicalized to name the output after the directory even if the parent is empty.
+            Some(parent) if v.file_name().is_some_and(|name| name == MANIFEST_FILE_NAME) => {
+                let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+                fs::canonicalize(parent).unwrap_or_else(|_| parent.to_path_buf())
+            },
+            _ => v.clone(),
+        }).map(|v| {
+            let relative_output = v.file_name().map(PathBuf::from).unwrap_or_else(|| v.clone());
+            KarincInput { path: v, relative_output }
+        }).collect()
+    }
+
+    fn write_file(path: &Path, content: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        fs::write(path, content).map_err(|e| io::Error::new(e.kind(), format!("failed to write `{}`: {}", path.display(), e)))
+    }
+}
@@ -0,0 +1,328 @@
+pub mod code;
+pub mod fix;
+pub mod json;
+pub mod message;
+pub mod render;
+
+use volt::parser::ParserError;
+use crate::ast::operator::OperationParserError;
+use crate::hako::{graph::HakoGraphError, loader::HakoLoaderError, manifest::HakoManifestError};
+use crate::hir::hirify::{TreeHirifierError, TreeHirifierLog};
+use crate::hir::resolve::UseResolverError;
+use crate::hir::type_check::{DataTypeError, DataTypeWarning};
+use crate::span::Span;
+use self::code::DiagnosticCode;
+use self::fix::fix_syntax_error;
+use self::message::{DiagnosticLanguage, DiagnosticMessage};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl DiagnosticSeverity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Note => "note",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticLabel {
+    pub span: Span,
+    pub message: Option<String>,
+}
+
+impl DiagnosticLabel {
+    pub fn new(span: Span, message: Option<String>) -> DiagnosticLabel {
+        DiagnosticLabel { span, message }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagnosticApplicability {
+    // Safe to apply without user confirmation.
+    MachineApplicable,
+    MaybeIncorrect,
+}
+
+impl DiagnosticApplicability {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticApplicability::MachineApplicable => "machine-applicable",
+            DiagnosticApplicability::MaybeIncorrect => "maybe-incorrect",
+        }
+    }
+}
+
+// Replaces the source in the span with the replacement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticSuggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: DiagnosticApplicability,
+}
+
+// Common form of errors and warnings which every compilation phase converts into.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    pub code: Option<DiagnosticCode>,
+    pub message: String,
+    // None when the problem cannot be located in source like an unmatched input.
+    pub primary_label: Option<DiagnosticLabel>,
+    pub secondary_labels: Vec<DiagnosticLabel>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+    pub suggestions: Vec<DiagnosticSuggestion>,
+    // Substituted into the catalog message of the code.
+    pub arguments: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: DiagnosticSeverity, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: None,
+            message,
+            primary_label: None,
+            secondary_labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+            suggestions: Vec::new(),
+            arguments: Vec::new(),
+        }
+    }
+
+    // Message, label and help are taken from the message catalog.
+    pub fn coded(severity: DiagnosticSeverity, code: DiagnosticCode, arguments: Vec<String>, span: Option<Span>) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(severity, String::new()).with_code(code);
+        diagnostic.arguments = arguments;
+        diagnostic.primary_label = span.map(|v| DiagnosticLabel::new(v, None));
+        diagnostic.localize(DiagnosticLanguage::default())
+    }
+
+    pub fn error(message: String) -> Diagnostic {
+        Diagnostic::new(DiagnosticSeverity::Error, message)
+    }
+
+    pub fn warning(message: String) -> Diagnostic {
+        Diagnostic::new(DiagnosticSeverity::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: DiagnosticCode) -> Diagnostic {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_primary_label(mut self, span: Span, message: Option<String>) -> Diagnostic {
+        self.primary_label = Some(DiagnosticLabel::new(span, message));
+        self
+    }
+
+    pub fn with_secondary_label(mut self, span: Span, message: Option<String>) -> Diagnostic {
+        self.secondary_labels.push(DiagnosticLabel::new(span, message));
+        self
+    }
+
+    pub fn with_note(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+
+    pub fn with_help(mut self, help: String) -> Diagnostic {
+        self.help = Some(help);
+        self
+    }
+
+    pub fn with_suggestion(mut self, message: String, span: Span, replacement: String, applicability: DiagnosticApplicability) -> Diagnostic {
+        self.suggestions.push(DiagnosticSuggestion { message, span, replacement, applicability });
+        self
+    }
+
+    // Diagnostics without code are left as they are.
+    // Label and help are replaced only when the catalog defines them so that the ones added by the caller are kept.
+    pub fn localize(mut self, language: DiagnosticLanguage) -> Diagnostic {
+        let code = match self.code {
+            Some(v) => v,
+            None => return self,
+        };
+
+        let message = DiagnosticMessage::get(code, language);
+        self.message = DiagnosticMessage::format(message.message, &self.arguments);
+
+        if let (Some(primary_label), Some(label)) = (&mut self.primary_label, message.label) {
+            primary_label.message = Some(DiagnosticMessage::format(label, &self.arguments));
+        }
+
+        if let Some(help) = message.help {
+            self.help = Some(DiagnosticMessage::format(help, &self.arguments));
+        }
+
+        if let Some(suggestion) = message.suggestion {
+            for each_suggestion in &mut self.suggestions {
+                each_suggestion.message = DiagnosticMessage::format(suggestion, &self.arguments);
+            }
+        }
+
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == DiagnosticSeverity::Error
+    }
+
+    // Suggestions of multiple errors in a literal like `_01_` replace the same span, so that applying them all breaks the source.
+    pub fn mark_conflicting_suggestions(diagnostics: &mut [Diagnostic]) {
+        let spans: Vec<Span> = diagnostics.iter().flat_map(|v| &v.suggestions).map(|v| v.span.clone()).collect();
+
+        for each_suggestion in diagnostics.iter_mut().flat_map(|v| &mut v.suggestions) {
+            if spans.iter().filter(|v| **v == each_suggestion.span).count() >= 2 {
+                each_suggestion.applicability = DiagnosticApplicability::MaybeIncorrect;
+            }
+        }
+    }
+}
+
+impl From<ParserError> for Diagnostic {
+    fn from(value: ParserError) -> Self {
+        match value {
+            ParserError::NoMatchedRule => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::NoMatchedRule, Vec::new(), None),
+            // Other errors come from the grammar rather than the input.
+            other => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::UnexpectedParserError, vec![format!("{:?}", other)], None),
+        }
+    }
+}
+
+impl From<TreeHirifierLog> for Diagnostic {
+    fn from(value: TreeHirifierLog) -> Self {
+        match value {
+            TreeHirifierLog::Error(e) => e.into(),
+        }
+    }
+}
+
+impl From<TreeHirifierError> for Diagnostic {
+    fn from(value: TreeHirifierError) -> Self {
+        let mut replacement = None;
+        // Span of the other definition which the error conflicts with.
+        let mut secondary_span = None;
+
+        let (code, arguments, span) = match value {
+            TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment, span } => (DiagnosticCode::PathSegmentMustLocateFirstPosition, vec![path_segment], span),
+            TreeHirifierError::SelfArgumentMustLocateFirstPosition { span } => (DiagnosticCode::SelfArgumentMustLocateFirstPosition, Vec::new(), span),
+            TreeHirifierError::InvalidOperation { error, span } => {
+                let code = match error {
+                    OperationParserError::InvalidKindOfTerm { .. } => DiagnosticCode::InvalidKindOfTerm,
+                    OperationParserError::InvalidLengthOfTerm { .. } => DiagnosticCode::InvalidLengthOfTerm,
+                    OperationParserError::NonAssociativeChain { .. } => DiagnosticCode::NonAssociativeChain,
+                    OperationParserError::UnbalancedGroup { .. } => DiagnosticCode::UnbalancedGroup,
+                };
+
+                (code, Vec::new(), span)
+            },
+            TreeHirifierError::InvalidSyntax { name, source, span } => {
+                let code = DiagnosticCode::from_syntax_error_name(&name).unwrap_or(DiagnosticCode::NoMatchedRule);
+                replacement = fix_syntax_error(code, &source);
+                (code, Vec::new(), span)
+            },
+            TreeHirifierError::DuplicateDefinition { name, previous, span } => {
+                secondary_span = Some(previous);
+                (DiagnosticCode::DuplicateDefinition, vec![name], span)
+            },
+            TreeHirifierError::ImportConflict { name, declaration, span } => {
+                secondary_span = Some(declaration);
+                (DiagnosticCode::ImportConflict, vec![name], span)
+            },
+        };
+
+        let mut diagnostic = Diagnostic::coded(DiagnosticSeverity::Error, code, arguments, Some(span.clone()));
+
+        if let Some(secondary_span) = secondary_span {
+            diagnostic = diagnostic.with_secondary_label(secondary_span, None);
+        }
+
+        match replacement {
+            // Message of the suggestion is taken from the catalog.
+            Some(v) => diagnostic.with_suggestion(String::new(), span, v, DiagnosticApplicability::MachineApplicable).localize(DiagnosticLanguage::default()),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<UseResolverError> for Diagnostic {
+    fn from(value: UseResolverError) -> Self {
+        match value {
+            UseResolverError::UnresolvedImport { path, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::UnresolvedImport, vec![path], Some(span)),
+            UseResolverError::AmbiguousImport { name, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::AmbiguousImport, vec![name], Some(span)),
+            UseResolverError::PrivateItemAccess { name, declaration, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::PrivateItemAccess, vec![name], Some(span))
+                .with_secondary_label(declaration, None),
+        }
+    }
+}
+
+impl From<DataTypeError> for Diagnostic {
+    fn from(value: DataTypeError) -> Self {
+        match value {
+            DataTypeError::UnknownIdentifier { span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::UnknownIdentifier, Vec::new(), Some(span)),
+            DataTypeError::UndeclaredDependency { hako, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::UndeclaredDependency, vec![hako], Some(span)),
+            DataTypeError::PrivateItemAccess { name, declaration, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::PrivateItemAccess, vec![name], Some(span))
+                .with_secondary_label(declaration, None),
+        }
+    }
+}
+
+impl From<DataTypeWarning> for Diagnostic {
+    fn from(value: DataTypeWarning) -> Self {
+        match value {
+            DataTypeWarning::UnusedImport { name, span } => Diagnostic::coded(DiagnosticSeverity::Warning, DiagnosticCode::UnusedImport, vec![name], Some(span)),
+        }
+    }
+}
+
+impl From<HakoLoaderError> for Diagnostic {
+    fn from(value: HakoLoaderError) -> Self {
+        match value {
+            HakoLoaderError::Io { path, message } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::FileReadFailed, vec![path.display().to_string(), message], None),
+            HakoLoaderError::InvalidId { path, id } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidModuleId, vec![id, path.display().to_string()], None),
+            HakoLoaderError::ParserError { path, error } => Diagnostic::from(error).with_note(format!("in `{}`", path.display())),
+            HakoLoaderError::Manifest(e) => Diagnostic::from(e),
+            HakoLoaderError::Graph(e) => Diagnostic::from(e),
+            HakoLoaderError::MissingEntry { path, entry } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::MissingEntryModule, vec![entry, path.display().to_string()], None),
+        }
+    }
+}
+
+impl From<HakoManifestError> for Diagnostic {
+    fn from(value: HakoManifestError) -> Self {
+        match value {
+            HakoManifestError::Io { path, message } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::FileReadFailed, vec![path.display().to_string(), message], None),
+            HakoManifestError::InvalidFormat { path, message, span } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidManifest, vec![path.display().to_string(), message.trim_end().to_string()], span),
+            HakoManifestError::InvalidName { path, name } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidHakoName, vec![name, path.display().to_string()], None),
+            HakoManifestError::InvalidVersion { path, version } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidHakoVersion, vec![version, path.display().to_string()], None),
+            HakoManifestError::InvalidEntry { path, entry } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidEntryModule, vec![entry, path.display().to_string()], None),
+            HakoManifestError::InvalidDependencyName { path, name } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::InvalidDependencyName, vec![name, path.display().to_string()], None),
+            HakoManifestError::SelfDependency { path, name } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::SelfDependency, vec![name, path.display().to_string()], None),
+        }
+    }
+}
+
+impl From<HakoGraphError> for Diagnostic {
+    fn from(value: HakoGraphError) -> Self {
+        match value {
+            HakoGraphError::Manifest(e) => Diagnostic::from(e),
+            HakoGraphError::DependencyCycle { names } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::DependencyCycle, vec![names.join(" -> ")], None),
+            HakoGraphError::DependencyNameMismatch { path, expected, found } => Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::DependencyNameMismatch, vec![expected, found, path.display().to_string()], None),
+            HakoGraphError::DuplicateName { name, paths } => {
+                let paths: Vec<String> = paths.iter().map(|v| format!("`{}`", v.display())).collect();
+                Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::DuplicateHakoName, vec![name, paths.join(", ")], None)
+            },
+        }
+    }
+}
@@ -0,0 +1,542 @@
+use std::fmt;
+
+// Codes are stable: never renumber or reuse them. Append new codes to the end.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DiagnosticCode {
+    NoMatchedRule,
+    PathSegmentMustLocateFirstPosition,
+    SelfArgumentMustLocateFirstPosition,
+    InvalidKindOfTerm,
+    InvalidLengthOfTerm,
+    NonAssociativeChain,
+    UnbalancedGroup,
+    UnknownIdentifier,
+    DigitSeparatorOnSide,
+    StartsWithZero,
+    HasCapitalLetter,
+    ExplicitPlusSymbol,
+    EndsWithZero,
+    InvalidItem,
+    InvalidFunctionBody,
+    InvalidModuleId,
+    DependencyCycle,
+    UndeclaredDependency,
+    UnresolvedImport,
+    AmbiguousImport,
+    ImportConflict,
+    UnusedImport,
+    PrivateItemAccess,
+    DuplicateDefinition,
+    FileReadFailed,
+    MissingEntryModule,
+    InvalidManifest,
+    InvalidHakoName,
+    InvalidHakoVersion,
+    InvalidEntryModule,
+    InvalidDependencyName,
+    SelfDependency,
+    DependencyNameMismatch,
+    DuplicateHakoName,
+    UnexpectedParserError,
+    StrayDocComment,
+}
+
+impl DiagnosticCode {
+    pub const ALL: &'static [DiagnosticCode] = &[
+        DiagnosticCode::NoMatchedRule,
+        DiagnosticCode::PathSegmentMustLocateFirstPosition,
+        DiagnosticCode::SelfArgumentMustLocateFirstPosition,
+        DiagnosticCode::InvalidKindOfTerm,
+        DiagnosticCode::InvalidLengthOfTerm,
+        DiagnosticCode::NonAssociativeChain,
+        DiagnosticCode::UnbalancedGroup,
+        DiagnosticCode::UnknownIdentifier,
+        DiagnosticCode::DigitSeparatorOnSide,
+        DiagnosticCode::StartsWithZero,
+        DiagnosticCode::HasCapitalLetter,
+        DiagnosticCode::ExplicitPlusSymbol,
+        DiagnosticCode::EndsWithZero,
+        DiagnosticCode::InvalidItem,
+        DiagnosticCode::InvalidFunctionBody,
+        DiagnosticCode::InvalidModuleId,
+        DiagnosticCode::DependencyCycle,
+        DiagnosticCode::UndeclaredDependency,
+        DiagnosticCode::UnresolvedImport,
+        DiagnosticCode::AmbiguousImport,
+        DiagnosticCode::ImportConflict,
+        DiagnosticCode::UnusedImport,
+        DiagnosticCode::PrivateItemAccess,
+        DiagnosticCode::DuplicateDefinition,
+        DiagnosticCode::FileReadFailed,
+        DiagnosticCode::MissingEntryModule,
+        DiagnosticCode::InvalidManifest,
+        DiagnosticCode::InvalidHakoName,
+        DiagnosticCode::InvalidHakoVersion,
+        DiagnosticCode::InvalidEntryModule,
+        DiagnosticCode::InvalidDependencyName,
+        DiagnosticCode::SelfDependency,
+        DiagnosticCode::DependencyNameMismatch,
+        DiagnosticCode::DuplicateHakoName,
+        DiagnosticCode::UnexpectedParserError,
+        DiagnosticCode::StrayDocComment,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            DiagnosticCode::NoMatchedRule => "K0001",
+            DiagnosticCode::PathSegmentMustLocateFirstPosition => "K0002",
+            DiagnosticCode::SelfArgumentMustLocateFirstPosition => "K0003",
+            DiagnosticCode::InvalidKindOfTerm => "K0004",
+            DiagnosticCode::InvalidLengthOfTerm => "K0005",
+            DiagnosticCode::NonAssociativeChain => "K0006",
+            DiagnosticCode::UnbalancedGroup => "K0007",
+            DiagnosticCode::UnknownIdentifier => "K0008",
+            DiagnosticCode::DigitSeparatorOnSide => "K0009",
+            DiagnosticCode::StartsWithZero => "K0010",
+            DiagnosticCode::HasCapitalLetter => "K0011",
+            DiagnosticCode::ExplicitPlusSymbol => "K0012",
+            DiagnosticCode::EndsWithZero => "K0013",
+            DiagnosticCode::InvalidItem => "K0014",
+            DiagnosticCode::InvalidFunctionBody => "K0015",
+            DiagnosticCode::InvalidModuleId => "K0016",
+            DiagnosticCode::DependencyCycle => "K0017",
+            DiagnosticCode::UndeclaredDependency => "K0018",
+            DiagnosticCode::UnresolvedImport => "K0019",
+            DiagnosticCode::AmbiguousImport => "K0020",
+            DiagnosticCode::ImportConflict => "K0021",
+            DiagnosticCode::UnusedImport => "K0022",
+            DiagnosticCode::PrivateItemAccess => "K0023",
+            DiagnosticCode::DuplicateDefinition => "K0024",
+            DiagnosticCode::FileReadFailed => "K0025",
+            DiagnosticCode::MissingEntryModule => "K0026",
+            DiagnosticCode::InvalidManifest => "K0027",
+            DiagnosticCode::InvalidHakoName => "K0028",
+            DiagnosticCode::InvalidHakoVersion => "K0029",
+            DiagnosticCode::InvalidEntryModule => "K0030",
+            DiagnosticCode::InvalidDependencyName => "K0031",
+            DiagnosticCode::SelfDependency => "K0032",
+            DiagnosticCode::DependencyNameMismatch => "K0033",
+            DiagnosticCode::DuplicateHakoName => "K0034",
+            DiagnosticCode::UnexpectedParserError => "K0035",
+            DiagnosticCode::StrayDocComment => "K0036",
+        }
+    }
+
+    // Accepts lowercase code like "k0001" too.
+    pub fn from_code(code: &str) -> Option<DiagnosticCode> {
+        DiagnosticCode::ALL.iter().find(|v| v.code().eq_ignore_ascii_case(code)).copied()
+    }
+
+    // Name of the error node generated by syntax reducers.
+    pub fn from_syntax_error_name(name: &str) -> Option<DiagnosticCode> {
+        let code = match name {
+            "digit_separator_on_side" => DiagnosticCode::DigitSeparatorOnSide,
+            "starts_with_zero" => DiagnosticCode::StartsWithZero,
+            "has_capital_letter" => DiagnosticCode::HasCapitalLetter,
+            "explicit_plus_symbol" => DiagnosticCode::ExplicitPlusSymbol,
+            "ends_with_zero" => DiagnosticCode::EndsWithZero,
+            "invalid_item" => DiagnosticCode::InvalidItem,
+            "invalid_function_body" => DiagnosticCode::InvalidFunctionBody,
+            "stray_doc_comment" => DiagnosticCode::StrayDocComment,
+            _ => return None,
+        };
+
+        Some(code)
+    }
+
+    // Long-form explanation printed by `--explain`.
+    // Explanations are written in English only. The message catalog localizes messages, labels and helps.
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            DiagnosticCode::NoMatchedRule => "\
+The input does not match any syntax rule and the parser cannot recover from it.
+
+Invalid items and function bodies are recovered and reported with their own codes,
+so that this is reported for example when the nesting of the input is too deep.
+",
+            DiagnosticCode::PathSegmentMustLocateFirstPosition => "\
+`hako` and `self` can only be the first segment of a path.
+
+Erroneous code example:
+
+    use a::hako::b
+
+Write the keyword at the beginning instead:
+
+    use hako::a::b
+",
+            DiagnosticCode::SelfArgumentMustLocateFirstPosition => "\
+`self` argument must be the first formal argument.
+
+Erroneous code example:
+
+    fn f(a usize, self) {}
+
+Move `self` to the beginning:
+
+    fn f(self, a usize) {}
+",
+            DiagnosticCode::InvalidKindOfTerm => "\
+The operator cannot take the kind of term.
+
+Path operator `::` takes identifiers only.
+
+Erroneous code example:
+
+    a::0
+",
+            DiagnosticCode::InvalidLengthOfTerm => "\
+The operation has a missing or extra term.
+
+Erroneous code example:
+
+    0 + + 1
+",
+            DiagnosticCode::NonAssociativeChain => "\
+Comparison operators are non-associative, so that they cannot be chained.
+
+Erroneous code example:
+
+    0 < a < 10
+
+Combine the comparisons with `&&` instead:
+
+    0 < a && a < 10
+",
+            DiagnosticCode::UnbalancedGroup => "\
+The parentheses in the operation are not balanced.
+
+Erroneous code example:
+
+    (0 + 1
+",
+            DiagnosticCode::UnknownIdentifier => "\
+The identifier cannot be found in this scope.
+
+Erroneous code example:
+
+    fn f() {
+        hako::g()
+    }
+
+Define the item or import it with `use`.
+",
+            DiagnosticCode::DigitSeparatorOnSide => "\
+Digit separator `_` must be located between digits.
+
+Erroneous code example:
+
+    _1_000_
+
+Remove the separators on both sides:
+
+    1_000
+",
+            DiagnosticCode::StartsWithZero => "\
+Decimal integer cannot start with zero.
+
+Erroneous code example:
+
+    012
+
+Remove the leading zero, or use `0o` prefix for octal numbers:
+
+    12
+    0o12
+",
+            DiagnosticCode::HasCapitalLetter => "\
+Hexadecimal digits must be written in lowercase.
+
+Erroneous code example:
+
+    0xFF
+
+Use lowercase letters instead:
+
+    0xff
+",
+            DiagnosticCode::ExplicitPlusSymbol => "\
+Positive exponent must not have an explicit plus symbol.
+
+Erroneous code example:
+
+    1e+3
+
+Remove the plus symbol:
+
+    1e3
+",
+            DiagnosticCode::EndsWithZero => "\
+Fractional part of float number cannot end with zero.
+
+Erroneous code example:
+
+    1.50
+
+Remove the trailing zero:
+
+    1.5
+",
+            DiagnosticCode::InvalidItem => "\
+The item cannot be parsed. The parser skips to the next line which starts with `fn`, `use` or `pub`.
+
+Erroneous code example:
+
+    fn f( {}
+",
+            DiagnosticCode::InvalidFunctionBody => "\
+The function body cannot be parsed. The parser skips to the closing brace of the body.
+
+Erroneous code example:
+
+    fn f() {
+        0 +
+    }
+",
+            DiagnosticCode::InvalidModuleId => "\
+Hako and module names are derived from directory and file names, and must be valid identifiers.
+
+Erroneous code example:
+
+    src/my-module.kr
+
+Rename the file to an identifier which is not a reserved keyword:
+
+    src/my_module.kr
+",
+            DiagnosticCode::DependencyCycle => "\
+Hakos cannot depend on each other directly or indirectly, since dependencies are compiled before the hakos which depend on them.
+
+Erroneous code example:
+
+    # a/hako.toml
+    [dependencies]
+    b = { path = \"../b\" }
+
+    # b/hako.toml
+    [dependencies]
+    a = { path = \"../a\" }
+
+Move the items shared by both hakos into another hako which neither of them depends on.
+",
+            DiagnosticCode::UndeclaredDependency => "\
+A path can only refer to the hako itself or the hakos declared in `[dependencies]` of its manifest.
+
+Erroneous code example:
+
+    # a/hako.toml has no dependency on `b`
+    fn f() {
+        b::g()
+    }
+
+Declare the hako as a dependency:
+
+    [dependencies]
+    b = { path = \"../b\" }
+",
+            DiagnosticCode::UnresolvedImport => "\
+The path of the use declaration does not refer to any item. Paths start with the name of a hako.
+
+Erroneous code example:
+
+    use app::unknown
+",
+            DiagnosticCode::AmbiguousImport => "\
+Different items are imported with the same name into a module. The name refers to the first imported item.
+
+Erroneous code example:
+
+    use a::f
+    use b::f
+",
+            DiagnosticCode::ImportConflict => "\
+The imported name is also defined in the module. The name refers to the item defined in the module.
+
+Erroneous code example:
+
+    use a::f
+
+    fn f() {}
+
+Import the item with another name:
+
+    use a::f as g
+",
+            DiagnosticCode::UnusedImport => "\
+The imported name is not used in the module. Glob imports are reported when none of the names are used.
+
+Example:
+
+    use a::f
+
+    fn main() {}
+",
+            DiagnosticCode::PrivateItemAccess => "\
+The item is not accessible from the module. Items without accessibility can only be used in the module which defines them and its submodules. Items with `pub@hako` can only be used in the same hako.
+
+Erroneous code example where `f` is defined in module `a`:
+
+    use hako::a::f
+
+Make the item public in module `a`:
+
+    pub fn f() {}
+",
+            DiagnosticCode::DuplicateDefinition => "\
+The name is defined multiple times in the module. Submodules and items share the same names. The name refers to the first definition.
+
+Erroneous code example:
+
+    fn f() {}
+
+    fn f() {}
+",
+            DiagnosticCode::FileReadFailed => "\
+A source file, a module directory or a manifest could not be read. The message of the operating system is attached.
+
+Check that the path exists and is readable.
+",
+            DiagnosticCode::MissingEntryModule => "\
+The source directory of the hako has no module named after `entry` of the manifest, which defaults to `main`.
+
+Erroneous example where `src/` has only `app.kr`:
+
+    [hako]
+    name = \"app\"
+    version = \"0.1.0\"
+    entry = \"main\"
+
+Add `src/main.kr` or set `entry` to an existing module.
+",
+            DiagnosticCode::InvalidManifest => "\
+The manifest is not valid TOML, or it has unknown keys or lacks required keys. `name` and `version` are required in `[hako]`.
+
+Erroneous example:
+
+    [hako]
+    name = \"app\"
+    edition = \"2024\"
+
+Remove the unknown keys and add the missing ones.
+",
+            DiagnosticCode::InvalidHakoName => "\
+The hako name must be an identifier to be referred by paths like `util::f`.
+
+Erroneous example:
+
+    [hako]
+    name = \"my-app\"
+    version = \"0.1.0\"
+
+Use an identifier like `my_app` instead.
+",
+            DiagnosticCode::InvalidHakoVersion => "\
+The hako version must consist of major, minor and patch numbers. Signs, leading zeros and pre-release suffixes are not accepted.
+
+Erroneous example:
+
+    [hako]
+    name = \"app\"
+    version = \"1.0\"
+
+Write the version like `1.0.0`.
+",
+            DiagnosticCode::InvalidEntryModule => "\
+The entry module must be an identifier since it is the name of a module file without its extension.
+
+Erroneous example:
+
+    [hako]
+    name = \"app\"
+    version = \"0.1.0\"
+    entry = \"main.kr\"
+
+Write the module name like `main`.
+",
+            DiagnosticCode::InvalidDependencyName => "\
+The dependency key must be an identifier since it is the name of the depended hako.
+
+Erroneous example:
+
+    [dependencies]
+    my-util = { path = \"../util\" }
+
+Use the name written in the manifest of the depended hako.
+",
+            DiagnosticCode::SelfDependency => "\
+A hako cannot depend on itself. Its own items are referred by `hako::` paths without a dependency.
+
+Erroneous example:
+
+    [hako]
+    name = \"app\"
+    version = \"0.1.0\"
+
+    [dependencies]
+    app = { path = \".\" }
+
+Remove the dependency.
+",
+            DiagnosticCode::DependencyNameMismatch => "\
+The dependency key must be the same as the name in the manifest of the depended hako, since paths refer to the hako by the key.
+
+Erroneous example:
+
+    # app/hako.toml
+    [dependencies]
+    utils = { path = \"../util\" }
+
+    # util/hako.toml
+    [hako]
+    name = \"util\"
+    version = \"0.1.0\"
+
+Rename the key to `util`.
+",
+            DiagnosticCode::DuplicateHakoName => "\
+Different hakos in the dependency graph have the same name. Hakos must have unique names to be distinguished by paths.
+
+Erroneous example:
+
+    # a/hako.toml
+    [hako]
+    name = \"util\"
+    version = \"0.1.0\"
+
+    # b/hako.toml
+    [hako]
+    name = \"util\"
+    version = \"0.1.0\"
+
+Rename one of the hakos.
+",
+            DiagnosticCode::UnexpectedParserError => "\
+The parser stopped for a reason other than the syntax of the input, like a rule which is not defined in the grammar.
+
+This is a bug of the compiler. Report it with the input which causes the error.
+",
+            DiagnosticCode::StrayDocComment => "\
+A doc comment must be followed by an item which it documents.
+
+Erroneous code example:
+
+    fn f() {
+        /// returns zero
+        0
+    }
+
+Use `//` for a plain comment.
+",
+        }
+    }
+
+    pub fn explain(code: &str) -> Option<String> {
+        DiagnosticCode::from_code(code).map(|v| format!("{}: {}", v.code(), v.explanation()))
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
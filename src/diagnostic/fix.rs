@@ -0,0 +1,36 @@
+use super::code::DiagnosticCode;
+
+// Generates the replacement of the source of error node.
+// Every fix keeps the value of the literal so that it is machine-applicable unless the literal has other errors.
+pub fn fix_syntax_error(code: DiagnosticCode, source: &str) -> Option<String> {
+    let replacement = match code {
+        DiagnosticCode::DigitSeparatorOnSide => source.trim_matches('_').to_string(),
+        DiagnosticCode::StartsWithZero => {
+            let trimmed = source.trim_start_matches(|v| v == '0' || v == '_');
+            non_empty_or_zero(trimmed)
+        },
+        DiagnosticCode::HasCapitalLetter => source.to_ascii_lowercase(),
+        DiagnosticCode::ExplicitPlusSymbol => source.replace('+', ""),
+        DiagnosticCode::EndsWithZero => {
+            let trimmed = source.trim_end_matches(|v| v == '0' || v == '_');
+            non_empty_or_zero(trimmed)
+        },
+        _ => return None,
+    };
+
+    // Nothing to fix when the source is already valid.
+    if replacement == source {
+        None
+    } else {
+        Some(replacement)
+    }
+}
+
+// All digits can be removed from the literal like "00".
+fn non_empty_or_zero(value: &str) -> String {
+    if value.is_empty() {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
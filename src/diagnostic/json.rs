@@ -0,0 +1,89 @@
+use serde_json::{json, Value};
+use crate::span::{SourceMap, SourcePosition, Span};
+use super::message::DiagnosticLanguage;
+use super::*;
+
+// Bump when a field is removed or its meaning changes. Adding a field does not break the schema.
+pub const DIAGNOSTIC_JSON_SCHEMA_VERSION: usize = 1;
+
+// Serializes diagnostics for editors and CI annotators.
+// Lines and columns are one-based like the rendered output, and offsets are zero-based character indexes.
+pub struct DiagnosticJsonWriter<'a> {
+    source_map: &'a SourceMap,
+    language: DiagnosticLanguage,
+}
+
+impl<'a> DiagnosticJsonWriter<'a> {
+    pub fn new(source_map: &'a SourceMap) -> DiagnosticJsonWriter<'a> {
+        DiagnosticJsonWriter { source_map, language: DiagnosticLanguage::default() }
+    }
+
+    pub fn with_language(mut self, language: DiagnosticLanguage) -> DiagnosticJsonWriter<'a> {
+        self.language = language;
+        self
+    }
+
+    pub fn write(&self, diagnostics: &[Diagnostic]) -> String {
+        serde_json::to_string_pretty(&self.value(diagnostics)).expect("diagnostic JSON must be serializable")
+    }
+
+    pub fn value(&self, diagnostics: &[Diagnostic]) -> Value {
+        json!({
+            "version": DIAGNOSTIC_JSON_SCHEMA_VERSION,
+            "diagnostics": diagnostics.iter().map(|v| self.diagnostic(v)).collect::<Vec<Value>>(),
+        })
+    }
+
+    pub fn diagnostic(&self, diagnostic: &Diagnostic) -> Value {
+        let diagnostic = &diagnostic.clone().localize(self.language);
+        let (file, range, label) = match &diagnostic.primary_label {
+            Some(label) => (self.file(&label.span), self.range(&label.span), json!(label.message)),
+            None => (Value::Null, Value::Null, Value::Null),
+        };
+
+        json!({
+            "code": diagnostic.code.map(|v| v.code()),
+            "severity": diagnostic.severity.name(),
+            "message": diagnostic.message,
+            "file": file,
+            "range": range,
+            "label": label,
+            "related": diagnostic.secondary_labels.iter().map(|v| json!({
+                "file": self.file(&v.span),
+                "range": self.range(&v.span),
+                "message": v.message,
+            })).collect::<Vec<Value>>(),
+            "notes": diagnostic.notes,
+            "help": diagnostic.help,
+            "suggestions": diagnostic.suggestions.iter().map(|v| json!({
+                "message": v.message,
+                "file": self.file(&v.span),
+                "range": self.range(&v.span),
+                "replacement": v.replacement,
+                "applicability": v.applicability.name(),
+            })).collect::<Vec<Value>>(),
+        })
+    }
+
+    fn file(&self, span: &Span) -> Value {
+        match self.source_map.get(span.file_id) {
+            Some(file) => json!(file.name),
+            None => Value::Null,
+        }
+    }
+
+    fn range(&self, span: &Span) -> Value {
+        json!({
+            "start": DiagnosticJsonWriter::position(&span.start),
+            "end": DiagnosticJsonWriter::position(&span.end),
+        })
+    }
+
+    fn position(position: &SourcePosition) -> Value {
+        json!({
+            "line": position.line + 1,
+            "column": position.column + 1,
+            "offset": position.index,
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use std::env;
+use super::code::DiagnosticCode;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DiagnosticLanguage {
+    #[default]
+    English,
+    Japanese,
+}
+
+impl DiagnosticLanguage {
+    pub const ALL: &'static [DiagnosticLanguage] = &[DiagnosticLanguage::English, DiagnosticLanguage::Japanese];
+
+    // Accepts language tags and POSIX locales like "ja", "ja-JP" and "ja_JP.UTF-8".
+    pub fn from_locale(locale: &str) -> Option<DiagnosticLanguage> {
+        let language = locale.split(|v| v == '_' || v == '-' || v == '.').next().unwrap_or("");
+
+        match language.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(DiagnosticLanguage::English),
+            "ja" => Some(DiagnosticLanguage::Japanese),
+            _ => None,
+        }
+    }
+
+    // KARIN_LANG takes priority over the locale variables in the same order as gettext.
+    pub fn from_env() -> DiagnosticLanguage {
+        for each_name in ["KARIN_LANG", "LC_ALL", "LC_MESSAGES", "LANG"] {
+            if let Ok(value) = env::var(each_name) {
+                if value.is_empty() {
+                    continue;
+                }
+
+                // Unknown locale falls back to English instead of looking up the next variable.
+                return DiagnosticLanguage::from_locale(&value).unwrap_or_default();
+            }
+        }
+
+        DiagnosticLanguage::default()
+    }
+}
+
+// Placeholders like `{0}` are replaced with the diagnostic arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticMessage {
+    pub message: &'static str,
+    pub label: Option<&'static str>,
+    pub help: Option<&'static str>,
+    // Message of the suggestions attached to the diagnostic.
+    pub suggestion: Option<&'static str>,
+}
+
+impl DiagnosticMessage {
+    pub fn format(template: &str, arguments: &[String]) -> String {
+        let mut formatted = template.to_string();
+
+        for (i, each_argument) in arguments.iter().enumerate() {
+            formatted = formatted.replace(&format!("{{{}}}", i), each_argument);
+        }
+
+        formatted
+    }
+
+    pub fn get(code: DiagnosticCode, language: DiagnosticLanguage) -> DiagnosticMessage {
+        let (message, label, help) = match language {
+            DiagnosticLanguage::English => DiagnosticMessage::english(code),
+            DiagnosticLanguage::Japanese => DiagnosticMessage::japanese(code),
+        };
+
+        let suggestion = match language {
+            DiagnosticLanguage::English => DiagnosticMessage::english_suggestion(code),
+            DiagnosticLanguage::Japanese => DiagnosticMessage::japanese_suggestion(code),
+        };
+
+        DiagnosticMessage { message, label, help, suggestion }
+    }
+
+    fn english(code: DiagnosticCode) -> (&'static str, Option<&'static str>, Option<&'static str>) {
+        match code {
+            DiagnosticCode::NoMatchedRule => ("input does not match any syntax rule", None, None),
+            DiagnosticCode::PathSegmentMustLocateFirstPosition => ("path segment `{0}` must be located at the first position", Some("not allowed here"), None),
+            DiagnosticCode::SelfArgumentMustLocateFirstPosition => ("`self` argument must be located at the first position", Some("not the first argument"), None),
+            DiagnosticCode::InvalidKindOfTerm => ("invalid kind of term in operation", Some("operator is not applicable to the term"), None),
+            DiagnosticCode::InvalidLengthOfTerm => ("missing or extra term in operation", Some("operator has an unexpected number of terms"), None),
+            DiagnosticCode::NonAssociativeChain => ("non-associative operators cannot be chained", Some("chained operator"), Some("separate the comparisons with `&&` or use parentheses")),
+            DiagnosticCode::UnbalancedGroup => ("unbalanced parentheses in operation", Some("unmatched parenthesis"), None),
+            DiagnosticCode::UnknownIdentifier => ("cannot find identifier in this scope", Some("not found"), None),
+            DiagnosticCode::DigitSeparatorOnSide => ("digit separator must be located between digits", Some("separator on the side"), None),
+            DiagnosticCode::StartsWithZero => ("decimal integer cannot start with zero", Some("leading zero"), Some("use `0o` prefix for octal numbers")),
+            DiagnosticCode::HasCapitalLetter => ("hexadecimal digits must be lowercase", Some("capital letter"), None),
+            DiagnosticCode::ExplicitPlusSymbol => ("positive exponent must not have plus symbol", Some("explicit plus symbol"), None),
+            DiagnosticCode::EndsWithZero => ("fractional part cannot end with zero", Some("trailing zero"), None),
+            DiagnosticCode::InvalidItem => ("invalid item", Some("skipped until the next item"), None),
+            DiagnosticCode::InvalidFunctionBody => ("invalid function body", Some("skipped until the end of the body"), None),
+            DiagnosticCode::InvalidModuleId => ("`{0}` is not a valid hako or module name", None, Some("rename `{1}` to an identifier")),
+            DiagnosticCode::DependencyCycle => ("cyclic dependency between hakos: {0}", None, Some("remove one of the dependencies in the cycle")),
+            DiagnosticCode::UndeclaredDependency => ("hako `{0}` is not a dependency of this hako", Some("undeclared dependency"), Some("add `{0}` to `[dependencies]` in the manifest")),
+            DiagnosticCode::UnresolvedImport => ("unresolved import `{0}`", Some("no such item"), None),
+            DiagnosticCode::AmbiguousImport => ("`{0}` is imported multiple times", Some("ambiguous import"), None),
+            DiagnosticCode::ImportConflict => ("`{0}` is imported but also defined in this module", Some("conflicting import"), Some("import it with another name by `as`")),
+            DiagnosticCode::UnusedImport => ("unused import `{0}`", Some("never used"), Some("remove the use declaration")),
+            DiagnosticCode::PrivateItemAccess => ("`{0}` is not accessible from here", Some("inaccessible item"), Some("make `{0}` public by `pub` or `pub@hako`")),
+            DiagnosticCode::DuplicateDefinition => ("`{0}` is defined multiple times", Some("redefined here"), Some("rename one of the definitions")),
+            DiagnosticCode::FileReadFailed => ("failed to read `{0}`: {1}", None, None),
+            DiagnosticCode::MissingEntryModule => ("entry module `{0}` is not found in `{1}`", None, Some("add `{0}.kr` or change `entry` in the manifest")),
+            DiagnosticCode::InvalidManifest => ("invalid manifest `{0}`: {1}", Some("invalid manifest"), None),
+            DiagnosticCode::InvalidHakoName => ("invalid hako name `{0}` in `{1}`", None, Some("rename the hako to an identifier")),
+            DiagnosticCode::InvalidHakoVersion => ("invalid version `{0}` in `{1}`: expected `major.minor.patch`", None, Some("write the version like `0.1.0`")),
+            DiagnosticCode::InvalidEntryModule => ("invalid entry module `{0}` in `{1}`", None, Some("set `entry` to a module name without the extension")),
+            DiagnosticCode::InvalidDependencyName => ("invalid dependency name `{0}` in `{1}`", None, Some("use the name of the depended hako")),
+            DiagnosticCode::SelfDependency => ("hako `{0}` depends on itself in `{1}`", None, Some("remove `{0}` from the dependencies")),
+            DiagnosticCode::DependencyNameMismatch => ("dependency `{0}` refers to hako `{1}` in `{2}`", None, Some("rename the dependency to `{1}`")),
+            DiagnosticCode::DuplicateHakoName => ("hako name `{0}` is used by multiple hakos: {1}", None, Some("rename one of the hakos")),
+            DiagnosticCode::UnexpectedParserError => ("parser stopped unexpectedly: {0}", None, Some("this is a bug of the compiler; report it with the input")),
+            DiagnosticCode::StrayDocComment => ("doc comment does not document any item", Some("not followed by an item"), Some("use `//` for a plain comment")),
+        }
+    }
+
+    fn japanese(code: DiagnosticCode) -> (&'static str, Option<&'static str>, Option<&'static str>) {
+        match code {
+            DiagnosticCode::NoMatchedRule => ("入力に一致する構文規則がありません", None, None),
+            DiagnosticCode::PathSegmentMustLocateFirstPosition => ("パスセグメント `{0}` は先頭に置く必要があります", Some("ここには置けません"), None),
+            DiagnosticCode::SelfArgumentMustLocateFirstPosition => ("`self` 引数は先頭に置く必要があります", Some("先頭の引数ではありません"), None),
+            DiagnosticCode::InvalidKindOfTerm => ("演算の項の種類が不正です", Some("この項には演算子を適用できません"), None),
+            DiagnosticCode::InvalidLengthOfTerm => ("演算の項が不足または過剰です", Some("演算子の項の数が不正です"), None),
+            DiagnosticCode::NonAssociativeChain => ("非結合の演算子は連鎖できません", Some("連鎖した演算子"), Some("比較を `&&` で区切るか括弧を使用してください")),
+            DiagnosticCode::UnbalancedGroup => ("演算の括弧が対応していません", Some("対応する括弧がありません"), None),
+            DiagnosticCode::UnknownIdentifier => ("このスコープに識別子が見つかりません", Some("見つかりません"), None),
+            DiagnosticCode::DigitSeparatorOnSide => ("桁区切りは数字の間に置く必要があります", Some("端にある桁区切り"), None),
+            DiagnosticCode::StartsWithZero => ("10進整数は0から始められません", Some("先頭のゼロ"), Some("8進数には `0o` 接頭辞を使用してください")),
+            DiagnosticCode::HasCapitalLetter => ("16進数の数字は小文字で書く必要があります", Some("大文字"), None),
+            DiagnosticCode::ExplicitPlusSymbol => ("正の指数にプラス記号は付けられません", Some("明示的なプラス記号"), None),
+            DiagnosticCode::EndsWithZero => ("小数部は0で終われません", Some("末尾のゼロ"), None),
+            DiagnosticCode::InvalidItem => ("不正なアイテムです", Some("次のアイテムまでスキップしました"), None),
+            DiagnosticCode::InvalidFunctionBody => ("不正な関数本体です", Some("本体の終わりまでスキップしました"), None),
+            DiagnosticCode::InvalidModuleId => ("`{0}` はハコまたはモジュールの名前として不正です", None, Some("`{1}` を識別子に名前変更してください")),
+            DiagnosticCode::DependencyCycle => ("ハコの依存関係が循環しています: {0}", None, Some("循環している依存関係のうち1つを削除してください")),
+            DiagnosticCode::UndeclaredDependency => ("ハコ `{0}` はこのハコの依存関係ではありません", Some("宣言されていない依存関係"), Some("マニフェストの `[dependencies]` に `{0}` を追加してください")),
+            DiagnosticCode::UnresolvedImport => ("インポート `{0}` を解決できません", Some("そのようなアイテムはありません"), None),
+            DiagnosticCode::AmbiguousImport => ("`{0}` が複数回インポートされています", Some("曖昧なインポート"), None),
+            DiagnosticCode::ImportConflict => ("`{0}` はインポートされていますがこのモジュールでも定義されています", Some("競合するインポート"), Some("`as` で別の名前を付けてインポートしてください")),
+            DiagnosticCode::UnusedImport => ("インポート `{0}` は使用されていません", Some("未使用"), Some("use 宣言を削除してください")),
+            DiagnosticCode::PrivateItemAccess => ("`{0}` にはここからアクセスできません", Some("アクセスできないアイテム"), Some("`pub` または `pub@hako` で `{0}` を公開してください")),
+            DiagnosticCode::DuplicateDefinition => ("`{0}` が複数回定義されています", Some("ここで再定義されています"), Some("いずれかの定義の名前を変更してください")),
+            DiagnosticCode::FileReadFailed => ("`{0}` を読み込めませんでした: {1}", None, None),
+            DiagnosticCode::MissingEntryModule => ("エントリーモジュール `{0}` が `{1}` に見つかりません", None, Some("`{0}.kr` を追加するかマニフェストの `entry` を変更してください")),
+            DiagnosticCode::InvalidManifest => ("マニフェスト `{0}` が不正です: {1}", Some("不正なマニフェスト"), None),
+            DiagnosticCode::InvalidHakoName => ("ハコ名 `{0}` が `{1}` で不正です", None, Some("ハコを識別子に名前変更してください")),
+            DiagnosticCode::InvalidHakoVersion => ("バージョン `{0}` が `{1}` で不正です: `major.minor.patch` の形式が必要です", None, Some("`0.1.0` のようにバージョンを記述してください")),
+            DiagnosticCode::InvalidEntryModule => ("エントリーモジュール `{0}` が `{1}` で不正です", None, Some("`entry` に拡張子を除いたモジュール名を設定してください")),
+            DiagnosticCode::InvalidDependencyName => ("依存関係の名前 `{0}` が `{1}` で不正です", None, Some("依存するハコの名前を使用してください")),
+            DiagnosticCode::SelfDependency => ("ハコ `{0}` が `{1}` で自身に依存しています", None, Some("依存関係から `{0}` を削除してください")),
+            DiagnosticCode::DependencyNameMismatch => ("依存関係 `{0}` が `{2}` でハコ `{1}` を参照しています", None, Some("依存関係を `{1}` に名前変更してください")),
+            DiagnosticCode::DuplicateHakoName => ("ハコ名 `{0}` が複数のハコで使用されています: {1}", None, Some("いずれかのハコの名前を変更してください")),
+            DiagnosticCode::UnexpectedParserError => ("パーサーが予期せず停止しました: {0}", None, Some("コンパイラのバグです。入力とともに報告してください")),
+            DiagnosticCode::StrayDocComment => ("ドキュメントコメントがどのアイテムにも付いていません", Some("後にアイテムがありません"), Some("通常のコメントには `//` を使用してください")),
+        }
+    }
+
+    fn english_suggestion(code: DiagnosticCode) -> Option<&'static str> {
+        match code {
+            DiagnosticCode::DigitSeparatorOnSide => Some("remove the separators on the side"),
+            DiagnosticCode::StartsWithZero => Some("remove the leading zero"),
+            DiagnosticCode::HasCapitalLetter => Some("write the digits in lowercase"),
+            DiagnosticCode::ExplicitPlusSymbol => Some("drop the explicit `+`"),
+            DiagnosticCode::EndsWithZero => Some("remove the trailing zero"),
+            _ => None,
+        }
+    }
+
+    fn japanese_suggestion(code: DiagnosticCode) -> Option<&'static str> {
+        match code {
+            DiagnosticCode::DigitSeparatorOnSide => Some("端の桁区切りを削除してください"),
+            DiagnosticCode::StartsWithZero => Some("先頭のゼロを削除してください"),
+            DiagnosticCode::HasCapitalLetter => Some("数字を小文字で書いてください"),
+            DiagnosticCode::ExplicitPlusSymbol => Some("明示的な `+` を削除してください"),
+            DiagnosticCode::EndsWithZero => Some("末尾のゼロを削除してください"),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use colored::*;
+use crate::span::SourceMap;
+use super::message::DiagnosticLanguage;
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RenderStyle {
+    Severity(DiagnosticSeverity),
+    Gutter,
+    SecondaryLabel,
+    Emphasis,
+}
+
+// Prints diagnostics with annotated source excerpts.
+// Colors are disabled in plain mode for CI logs and files.
+pub struct DiagnosticRenderer<'a> {
+    source_map: &'a SourceMap,
+    colored: bool,
+    language: DiagnosticLanguage,
+}
+
+impl<'a> DiagnosticRenderer<'a> {
+    pub fn new(source_map: &'a SourceMap, colored: bool) -> DiagnosticRenderer<'a> {
+        DiagnosticRenderer { source_map, colored, language: DiagnosticLanguage::default() }
+    }
+
+    pub fn plain(source_map: &'a SourceMap) -> DiagnosticRenderer<'a> {
+        DiagnosticRenderer::new(source_map, false)
+    }
+
+    pub fn with_language(mut self, language: DiagnosticLanguage) -> DiagnosticRenderer<'a> {
+        self.language = language;
+        self
+    }
+
+    pub fn render_all(&self, diagnostics: &[Diagnostic]) -> String {
+        diagnostics.iter().map(|v| self.render(v)).collect::<Vec<String>>().join("\n")
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let diagnostic = &diagnostic.clone().localize(self.language);
+        let mut output = String::new();
+        let severity_style = RenderStyle::Severity(diagnostic.severity);
+
+        let header = match &diagnostic.code {
+            Some(code) => format!("{}[{}]", diagnostic.severity.name(), code),
+            None => diagnostic.severity.name().to_string(),
+        };
+
+        output += &format!("{}{}\n", self.paint(&header, severity_style), self.paint(&format!(": {}", diagnostic.message), RenderStyle::Emphasis));
+
+        let mut labels: Vec<(&DiagnosticLabel, bool)> = Vec::new();
+
+        if let Some(primary_label) = &diagnostic.primary_label {
+            labels.push((primary_label, true));
+        }
+
+        labels.extend(diagnostic.secondary_labels.iter().map(|v| (v, false)));
+
+        // Width of the largest one-based line number.
+        let gutter_width = labels.iter().map(|(v, _)| (v.span.start.line + 1).to_string().len()).max().unwrap_or(0);
+        let empty_gutter = format!("{} |", " ".repeat(gutter_width));
+
+        if let Some((primary_label, _)) = labels.first() {
+            let span = &primary_label.span;
+            let file_name = match self.source_map.get(span.file_id) {
+                Some(file) => file.name.clone(),
+                None => format!("<file {}>", span.file_id),
+            };
+
+            output += &format!("{}{} {}:{}:{}\n", " ".repeat(gutter_width), self.paint("-->", RenderStyle::Gutter), file_name, span.start.line + 1, span.start.column + 1);
+        }
+
+        let mut sorted_labels = labels.clone();
+        sorted_labels.sort_by_key(|(v, _)| (v.span.file_id, v.span.start.line, v.span.start.column));
+        let mut last_line = None;
+
+        for (label, is_primary) in sorted_labels {
+            let span = &label.span;
+
+            let line = match self.source_map.get(span.file_id).and_then(|v| v.line(span.start.line)) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if last_line != Some((span.file_id, span.start.line)) {
+                output += &format!("{}\n", self.paint(&empty_gutter, RenderStyle::Gutter));
+                let line_number = format!("{:>width$} |", span.start.line + 1, width = gutter_width);
+                output += &format!("{} {}\n", self.paint(&line_number, RenderStyle::Gutter), line);
+                last_line = Some((span.file_id, span.start.line));
+            }
+
+            // Multiline spans are underlined until the end of the first line.
+            let line_len = line.chars().count();
+            let underline_start = span.start.column.min(line_len);
+            let underline_end = if span.end.line == span.start.line { span.end.column.min(line_len) } else { line_len };
+            let underline_len = underline_end.saturating_sub(underline_start).max(1);
+
+            let (marker, style) = if is_primary {
+                ("^", severity_style)
+            } else {
+                ("-", RenderStyle::SecondaryLabel)
+            };
+
+            let mut underline = marker.repeat(underline_len);
+
+            if let Some(message) = &label.message {
+                underline += &format!(" {}", message);
+            }
+
+            output += &format!("{} {}{}\n", self.paint(&empty_gutter, RenderStyle::Gutter), " ".repeat(underline_start), self.paint(&underline, style));
+        }
+
+        let has_footer = diagnostic.notes.len() != 0 || diagnostic.help.is_some() || diagnostic.suggestions.len() != 0;
+
+        if last_line.is_some() && has_footer {
+            output += &format!("{}\n", self.paint(&empty_gutter, RenderStyle::Gutter));
+        }
+
+        for each_note in &diagnostic.notes {
+            output += &format!("{} {}: {}\n", self.paint(&format!("{} =", " ".repeat(gutter_width)), RenderStyle::Gutter), self.paint("note", RenderStyle::Emphasis), each_note);
+        }
+
+        if let Some(help) = &diagnostic.help {
+            output += &format!("{} {}: {}\n", self.paint(&format!("{} =", " ".repeat(gutter_width)), RenderStyle::Gutter), self.paint("help", RenderStyle::Emphasis), help);
+        }
+
+        // Replacements are shown inline since they do not span multiple lines.
+        for each_suggestion in &diagnostic.suggestions {
+            output += &format!("{} {}: {}: `{}`\n", self.paint(&format!("{} =", " ".repeat(gutter_width)), RenderStyle::Gutter), self.paint("help", RenderStyle::Emphasis), each_suggestion.message, each_suggestion.replacement);
+        }
+
+        output
+    }
+
+    fn paint(&self, text: &str, style: RenderStyle) -> String {
+        if !self.colored {
+            return text.to_string();
+        }
+
+        let colored_text = match style {
+            RenderStyle::Severity(DiagnosticSeverity::Error) => text.red().bold(),
+            RenderStyle::Severity(DiagnosticSeverity::Warning) => text.yellow().bold(),
+            RenderStyle::Severity(DiagnosticSeverity::Note) => text.green().bold(),
+            RenderStyle::Gutter | RenderStyle::SecondaryLabel => text.blue().bold(),
+            RenderStyle::Emphasis => text.bold(),
+        };
+
+        colored_text.to_string()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod graph;
+pub mod loader;
+pub mod manifest;
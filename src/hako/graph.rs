@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fmt, fs, path::{Path, PathBuf}};
+use crate::span::SourceMap;
+use super::manifest::{HakoManifest, HakoManifestError, MANIFEST_FILE_NAME};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HakoGraphError {
+    Manifest(HakoManifestError),
+    // Names are listed along the cycle like "a -> b -> a".
+    DependencyCycle { names: Vec<String> },
+    // Dependency key in the manifest differs from the name of the depended hako.
+    DependencyNameMismatch { path: PathBuf, expected: String, found: String },
+    // Different hakos cannot have the same name to be distinguished by paths.
+    DuplicateName { name: String, paths: Vec<PathBuf> },
+}
+
+impl fmt::Display for HakoGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HakoGraphError::Manifest(e) => write!(f, "{}", e),
+            HakoGraphError::DependencyCycle { names } => write!(f, "cyclic dependency between hakos: {}", names.join(" -> ")),
+            HakoGraphError::DependencyNameMismatch { path, expected, found } => write!(f, "dependency `{}` refers to hako `{}` in `{}`", expected, found, path.display()),
+            HakoGraphError::DuplicateName { name, paths } => {
+                let paths: Vec<String> = paths.iter().map(|v| format!("`{}`", v.display())).collect();
+                write!(f, "hako name `{}` is used by multiple hakos: {}", name, paths.join(", "))
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HakoGraphNode {
+    pub manifest: HakoManifest,
+    // Indexes of the nodes in the graph.
+    pub dependencies: Vec<usize>,
+}
+
+// Hakos which the root hako depends on directly or indirectly. The root is located at index 0.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HakoGraph {
+    pub nodes: Vec<HakoGraphNode>,
+}
+
+impl HakoGraph {
+    pub fn new() -> HakoGraph {
+        HakoGraph::default()
+    }
+
+    pub fn add(&mut self, manifest: HakoManifest) -> usize {
+        self.nodes.push(HakoGraphNode { manifest, dependencies: Vec::new() });
+        self.nodes.len() - 1
+    }
+
+    pub fn add_dependency(&mut self, from: usize, to: usize) {
+        self.nodes[from].dependencies.push(to);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&HakoGraphNode> {
+        self.nodes.get(index)
+    }
+
+    // Names of the hakos which the hako depends on directly.
+    pub fn dependency_names(&self, index: usize) -> Vec<String> {
+        self.nodes[index].dependencies.iter().map(|v| self.nodes[*v].manifest.name.clone()).collect()
+    }
+
+    // Loads manifests of the root hako and its dependencies.
+    // Path is the manifest file or the directory which contains it.
+    // Manifest files are added to the source map to locate their errors.
+    pub fn load(path: &Path, source_map: &mut SourceMap) -> Result<HakoGraph, Vec<HakoGraphError>> {
+        let mut graph = HakoGraph::new();
+        let mut errors = Vec::new();
+        // Canonical directory of each loaded hako.
+        let mut directories: BTreeMap<PathBuf, usize> = BTreeMap::new();
+
+        let root_directory = HakoGraph::directory(path);
+
+        match HakoManifest::load(&root_directory, source_map) {
+            Ok(v) => {
+                directories.insert(HakoGraph::canonicalize(&root_directory), graph.add(v));
+            },
+            Err(e) => return Err(e.into_iter().map(|v| HakoGraphError::Manifest(v)).collect()),
+        }
+
+        // Nodes are appended while traversing.
+        let mut next = 0;
+
+        while next < graph.nodes.len() {
+            let dependencies = graph.nodes[next].manifest.dependencies.clone();
+
+            for each_dependency in dependencies {
+                let directory = HakoGraph::canonicalize(&each_dependency.path);
+
+                let index = match directories.get(&directory) {
+                    Some(v) => *v,
+                    None => match HakoManifest::load(&directory, source_map) {
+                        Ok(v) => {
+                            let index = graph.add(v);
+                            directories.insert(directory.clone(), index);
+                            index
+                        },
+                        Err(e) => {
+                            errors.extend(e.into_iter().map(|v| HakoGraphError::Manifest(v)));
+                            continue;
+                        },
+                    },
+                };
+
+                let found = &graph.nodes[index].manifest.name;
+
+                if *found != each_dependency.name {
+                    errors.push(HakoGraphError::DependencyNameMismatch {
+                        path: directory.join(MANIFEST_FILE_NAME),
+                        expected: each_dependency.name.clone(),
+                        found: found.clone(),
+                    });
+                }
+
+                graph.add_dependency(next, index);
+            }
+
+            next += 1;
+        }
+
+        errors.extend(HakoGraph::duplicate_names(&directories, &graph));
+
+        if let Err(e) = graph.topological_order() {
+            errors.push(e);
+        }
+
+        if errors.len() != 0 {
+            return Err(errors);
+        }
+
+        Ok(graph)
+    }
+
+    // Dependencies come before the hakos which depend on them.
+    pub fn topological_order(&self) -> Result<Vec<usize>, HakoGraphError> {
+        let mut states = vec![VisitState::Unvisited; self.nodes.len()];
+        let mut order = Vec::new();
+        let mut stack = Vec::new();
+
+        for each_index in 0..self.nodes.len() {
+            self.visit(each_index, &mut states, &mut stack, &mut order)?;
+        }
+
+        Ok(order)
+    }
+
+    fn visit(&self, index: usize, states: &mut [VisitState], stack: &mut Vec<usize>, order: &mut Vec<usize>) -> Result<(), HakoGraphError> {
+        match states[index] {
+            VisitState::Visited => return Ok(()),
+            VisitState::Visiting => {
+                // Cycle starts from the first appearance of the hako in the stack.
+                let start = stack.iter().position(|v| *v == index).unwrap_or(0);
+                let names = stack[start..].iter().chain([index].iter()).map(|v| self.nodes[*v].manifest.name.clone()).collect();
+                return Err(HakoGraphError::DependencyCycle { names });
+            },
+            VisitState::Unvisited => (),
+        }
+
+        states[index] = VisitState::Visiting;
+        stack.push(index);
+
+        for each_dependency in &self.nodes[index].dependencies {
+            self.visit(*each_dependency, states, stack, order)?;
+        }
+
+        stack.pop();
+        states[index] = VisitState::Visited;
+        order.push(index);
+        Ok(())
+    }
+
+    fn duplicate_names(directories: &BTreeMap<PathBuf, usize>, graph: &HakoGraph) -> Vec<HakoGraphError> {
+        let mut paths_by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+        for (each_directory, each_index) in directories {
+            paths_by_name.entry(graph.nodes[*each_index].manifest.name.clone()).or_default().push(each_directory.clone());
+        }
+
+        paths_by_name.into_iter()
+            .filter(|(_, paths)| paths.len() >= 2)
+            .map(|(name, paths)| HakoGraphError::DuplicateName { name, paths })
+            .collect()
+    }
+
+    fn directory(path: &Path) -> PathBuf {
+        match path.file_name() {
+            Some(v) if v == MANIFEST_FILE_NAME => match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            },
+            _ => path.to_path_buf(),
+        }
+    }
+
+    // Same hako can be referred by different relative paths.
+    fn canonicalize(path: &Path) -> PathBuf {
+        fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum VisitState {
+    Unvisited,
+    Visiting,
+    Visited,
+}
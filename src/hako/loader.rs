@@ -0,0 +1,216 @@
+use std::{fs, path::{Path, PathBuf}};
+use volt::{Volt, parser::ParserError, rule::RuleId, tree::SyntaxTree};
+use crate::ast::syntax::Syntax;
+use crate::hir::hirify::{AstHako, AstModule};
+use crate::span::{FileId, SourceMap};
+use super::graph::{HakoGraph, HakoGraphError};
+use super::manifest::{HakoManifest, HakoManifestError};
+
+pub const SOURCE_EXTENSION: &str = "kr";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HakoLoaderError {
+    Io { path: PathBuf, message: String },
+    // Hako and module ids must be identifiers to be referred by paths.
+    InvalidId { path: PathBuf, id: String },
+    ParserError { path: PathBuf, error: ParserError },
+    Manifest(HakoManifestError),
+    Graph(HakoGraphError),
+    MissingEntry { path: PathBuf, entry: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadedHako {
+    pub id: String,
+    pub path: PathBuf,
+    pub dependencies: Vec<String>,
+    pub modules: Vec<LoadedModule>,
+}
+
+impl LoadedHako {
+    pub fn ast(&self) -> AstHako<'_> {
+        AstHako {
+            id: self.id.clone(),
+            dependencies: self.dependencies.clone(),
+            modules: self.modules.iter().map(|v| v.ast()).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadedModule {
+    pub id: String,
+    // Directory without the module file has an empty tree and no file.
+    pub path: Option<PathBuf>,
+    pub file_id: FileId,
+    pub tree: SyntaxTree,
+    pub submodules: Vec<LoadedModule>,
+}
+
+impl LoadedModule {
+    pub fn ast(&self) -> AstModule<'_> {
+        AstModule {
+            id: self.id.clone(),
+            file_id: self.file_id,
+            node: &self.tree.root,
+            submodules: self.submodules.iter().map(|v| v.ast()).collect(),
+        }
+    }
+}
+
+// Maps a hako directory onto modules:
+//
+//     hako/
+//         a.kr      -> a
+//         a/
+//             b.kr  -> a::b
+//         c/
+//             d.kr  -> c::d (c is an empty module)
+//
+// Modules with invalid ids are skipped so that the rest of modules are still analyzed.
+pub struct HakoLoader<'a> {
+    source_map: &'a mut SourceMap,
+    volt: Volt,
+    errors: Vec<HakoLoaderError>,
+}
+
+impl<'a> HakoLoader<'a> {
+    pub fn new(source_map: &'a mut SourceMap) -> HakoLoader<'a> {
+        HakoLoader {
+            source_map,
+            volt: Syntax::generate_volt(1024),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn load(mut self, id: &str, path: &Path) -> (LoadedHako, Vec<HakoLoaderError>) {
+        self.validate_id(id, path);
+        let modules = self.modules(path);
+        let hako = LoadedHako { id: id.to_string(), path: path.to_path_buf(), dependencies: Vec::new(), modules };
+        (hako, self.errors)
+    }
+
+    // Loads modules in the source directory of the manifest.
+    pub fn load_manifest(self, manifest: &HakoManifest) -> (LoadedHako, Vec<HakoLoaderError>) {
+        let (mut hako, mut errors) = self.load(&manifest.name, &manifest.source);
+        hako.dependencies = manifest.dependencies.iter().map(|v| v.name.clone()).collect();
+
+        if !hako.modules.iter().any(|v| v.id == manifest.entry) {
+            errors.push(HakoLoaderError::MissingEntry { path: manifest.source.clone(), entry: manifest.entry.clone() });
+        }
+
+        (hako, errors)
+    }
+
+    // Loads all hakos in the graph so that dependencies come before the hakos which depend on them.
+    pub fn load_graph(source_map: &mut SourceMap, graph: &HakoGraph) -> (Vec<LoadedHako>, Vec<HakoLoaderError>) {
+        let order = match graph.topological_order() {
+            Ok(v) => v,
+            Err(e) => return (Vec::new(), vec![HakoLoaderError::Graph(e)]),
+        };
+
+        let mut hakos = Vec::new();
+        let mut errors = Vec::new();
+
+        for each_index in order {
+            let (hako, mut hako_errors) = HakoLoader::new(source_map).load_manifest(&graph.nodes[each_index].manifest);
+            hakos.push(hako);
+            errors.append(&mut hako_errors);
+        }
+
+        (hakos, errors)
+    }
+
+    // Loads a single file as a hako which has the only module.
+    pub fn load_file(mut self, id: &str, path: &Path) -> (LoadedHako, Vec<HakoLoaderError>) {
+        self.validate_id(id, path);
+        let modules = self.module(path, None).into_iter().collect();
+        let hako = LoadedHako { id: id.to_string(), path: path.to_path_buf(), dependencies: Vec::new(), modules };
+        (hako, self.errors)
+    }
+
+    // Id of the hako or the module which is derived from the file name.
+    pub fn id_from_path(path: &Path) -> String {
+        path.file_stem().map(|v| v.to_string_lossy().to_string()).unwrap_or_default()
+    }
+
+    fn modules(&mut self, directory: &Path) -> Vec<LoadedModule> {
+        let mut entries: Vec<PathBuf> = match fs::read_dir(directory) {
+            Ok(v) => v.filter_map(|entry| entry.ok().map(|v| v.path())).collect(),
+            Err(e) => {
+                self.errors.push(HakoLoaderError::Io { path: directory.to_path_buf(), message: e.to_string() });
+                return Vec::new();
+            },
+        };
+
+        entries.sort();
+        let mut modules = Vec::new();
+
+        for each_entry in &entries {
+            let is_hidden = each_entry.file_name().is_some_and(|v| v.to_string_lossy().starts_with('.'));
+
+            if is_hidden {
+                continue;
+            }
+
+            if each_entry.is_dir() {
+                // Directory with the module file is loaded together with the file.
+                if !entries.contains(&each_entry.with_extension(SOURCE_EXTENSION)) {
+                    modules.extend(self.module(each_entry, Some(each_entry)));
+                }
+            } else if each_entry.extension().is_some_and(|v| v == SOURCE_EXTENSION) {
+                let directory = each_entry.with_extension("");
+                let submodule_directory = if directory.is_dir() { Some(directory.as_path()) } else { None };
+                modules.extend(self.module(each_entry, submodule_directory));
+            }
+        }
+
+        modules
+    }
+
+    // Path is the module file, or the directory when the file does not exist.
+    fn module(&mut self, path: &Path, directory: Option<&Path>) -> Option<LoadedModule> {
+        let id = HakoLoader::id_from_path(path);
+        let is_valid_id = self.validate_id(&id, path);
+        let submodules = directory.map(|v| self.modules(v)).unwrap_or_default();
+
+        // Broken module file is loaded as an empty module so that its submodules are still analyzed.
+        let (file_path, content) = if path.is_dir() {
+            (None, String::new())
+        } else {
+            match fs::read_to_string(path) {
+                Ok(v) => (Some(path.to_path_buf()), v),
+                Err(e) => {
+                    self.errors.push(HakoLoaderError::Io { path: path.to_path_buf(), message: e.to_string() });
+                    (Some(path.to_path_buf()), String::new())
+                },
+            }
+        };
+
+        let file_id = self.source_map.add(path.display().to_string(), content.clone());
+
+        let tree = match self.volt.parse(&content, &RuleId("Main::main".to_string())) {
+            Ok(v) => v,
+            Err(e) => {
+                self.errors.push(HakoLoaderError::ParserError { path: path.to_path_buf(), error: e });
+                self.volt.parse("", &RuleId("Main::main".to_string())).expect("empty module must be parsed")
+            },
+        };
+
+        if !is_valid_id {
+            return None;
+        }
+
+        Some(LoadedModule { id, path: file_path, file_id, tree, submodules })
+    }
+
+    fn validate_id(&mut self, id: &str, path: &Path) -> bool {
+        let is_valid = Syntax::is_identifier(id);
+
+        if !is_valid {
+            self.errors.push(HakoLoaderError::InvalidId { path: path.to_path_buf(), id: id.to_string() });
+        }
+
+        is_valid
+    }
+}
@@ -0,0 +1,221 @@
+use std::{collections::BTreeMap, fmt, fs, path::{Path, PathBuf}};
+use serde::Deserialize;
+use crate::ast::syntax::Syntax;
+use crate::js::JsTranspilerOptions;
+use crate::span::{FileId, SourceMap, SourcePosition, Span};
+
+pub const MANIFEST_FILE_NAME: &str = "hako.toml";
+pub const DEFAULT_ENTRY: &str = "main";
+pub const DEFAULT_SOURCE: &str = "src";
+
+// Declares a hako in its root directory:
+//
+//     [hako]
+//     name = "app"
+//     version = "0.1.0"
+//     entry = "main"
+//     source = "src"
+//
+//     [dependencies]
+//     util = { path = "../util" }
+//
+//     [options]
+//     checked-arithmetic = true
+#[derive(Clone, Debug, PartialEq)]
+pub struct HakoManifest {
+    pub name: String,
+    pub version: HakoVersion,
+    // Id of the module which the program starts from.
+    pub entry: String,
+    // Directory of modules which is resolved from the manifest directory.
+    pub source: PathBuf,
+    pub dependencies: Vec<HakoDependency>,
+    pub options: HakoOptions,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HakoDependency {
+    pub name: String,
+    // Resolved from the manifest directory.
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HakoOptions {
+    pub checked_arithmetic: bool,
+}
+
+impl From<&HakoOptions> for JsTranspilerOptions {
+    fn from(value: &HakoOptions) -> Self {
+        JsTranspilerOptions { checked_arithmetic: value.checked_arithmetic }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct HakoVersion {
+    pub major: usize,
+    pub minor: usize,
+    pub patch: usize,
+}
+
+impl HakoVersion {
+    // Accepts "major.minor.patch" only.
+    pub fn parse(version: &str) -> Option<HakoVersion> {
+        let numbers: Vec<usize> = version.split('.').map(|v| {
+            // Rejects signs and leading zeros which str::parse accepts.
+            let is_valid = !v.is_empty() && v.chars().all(|ch| ch.is_ascii_digit()) && (v == "0" || !v.starts_with('0'));
+            if is_valid { v.parse().ok() } else { None }
+        }).collect::<Option<_>>()?;
+
+        match numbers[..] {
+            [major, minor, patch] => Some(HakoVersion { major, minor, patch }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HakoVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HakoManifestError {
+    Io { path: PathBuf, message: String },
+    // Malformed TOML or unknown or missing keys. Span is located in the manifest file when TOML reports it.
+    InvalidFormat { path: PathBuf, message: String, span: Option<Span> },
+    InvalidName { path: PathBuf, name: String },
+    InvalidVersion { path: PathBuf, version: String },
+    InvalidEntry { path: PathBuf, entry: String },
+    InvalidDependencyName { path: PathBuf, name: String },
+    // Hako cannot depend on itself by name.
+    SelfDependency { path: PathBuf, name: String },
+}
+
+impl fmt::Display for HakoManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HakoManifestError::Io { path, message } => write!(f, "failed to read `{}`: {}", path.display(), message),
+            HakoManifestError::InvalidFormat { path, message, .. } => write!(f, "invalid manifest `{}`: {}", path.display(), message.trim_end()),
+            HakoManifestError::InvalidName { path, name } => write!(f, "invalid hako name `{}` in `{}`", name, path.display()),
+            HakoManifestError::InvalidVersion { path, version } => write!(f, "invalid version `{}` in `{}`: expected `major.minor.patch`", version, path.display()),
+            HakoManifestError::InvalidEntry { path, entry } => write!(f, "invalid entry module `{}` in `{}`", entry, path.display()),
+            HakoManifestError::InvalidDependencyName { path, name } => write!(f, "invalid dependency name `{}` in `{}`", name, path.display()),
+            HakoManifestError::SelfDependency { path, name } => write!(f, "hako `{}` depends on itself in `{}`", name, path.display()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawManifest {
+    hako: RawHako,
+    #[serde(default)]
+    dependencies: BTreeMap<String, RawDependency>,
+    #[serde(default)]
+    options: RawOptions,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawHako {
+    name: String,
+    version: String,
+    entry: Option<String>,
+    source: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDependency {
+    path: PathBuf,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawOptions {
+    #[serde(default)]
+    checked_arithmetic: bool,
+}
+
+impl HakoManifest {
+    // Path is the manifest file or the directory which contains it.
+    // The manifest file is added to the source map to locate its errors.
+    pub fn load(path: &Path, source_map: &mut SourceMap) -> Result<HakoManifest, Vec<HakoManifestError>> {
+        let path = if path.is_dir() { path.join(MANIFEST_FILE_NAME) } else { path.to_path_buf() };
+
+        match fs::read_to_string(&path) {
+            Ok(v) => {
+                let file_id = source_map.add(path.display().to_string(), v.clone());
+                HakoManifest::parse_in_file(&v, &path, file_id)
+            },
+            Err(e) => Err(vec![HakoManifestError::Io { path, message: e.to_string() }]),
+        }
+    }
+
+    // Relative paths in the manifest are resolved from the directory of the path.
+    pub fn parse(content: &str, path: &Path) -> Result<HakoManifest, Vec<HakoManifestError>> {
+        HakoManifest::parse_in_file(content, path, FileId::default())
+    }
+
+    // Same as `parse()` but locates format errors in the file.
+    pub fn parse_in_file(content: &str, path: &Path, file_id: FileId) -> Result<HakoManifest, Vec<HakoManifestError>> {
+        let raw: RawManifest = match toml::from_str(content) {
+            Ok(v) => v,
+            Err(e) => {
+                let span = e.span().map(|v| Span::new(
+                    file_id,
+                    SourcePosition::from_byte_index(content, v.start),
+                    SourcePosition::from_byte_index(content, v.end),
+                ));
+
+                return Err(vec![HakoManifestError::InvalidFormat { path: path.to_path_buf(), message: e.message().to_string(), span }]);
+            },
+        };
+
+        let directory = path.parent().unwrap_or(Path::new(""));
+        let mut errors = Vec::new();
+
+        if !Syntax::is_identifier(&raw.hako.name) {
+            errors.push(HakoManifestError::InvalidName { path: path.to_path_buf(), name: raw.hako.name.clone() });
+        }
+
+        let version = HakoVersion::parse(&raw.hako.version);
+
+        if version.is_none() {
+            errors.push(HakoManifestError::InvalidVersion { path: path.to_path_buf(), version: raw.hako.version.clone() });
+        }
+
+        let entry = raw.hako.entry.unwrap_or_else(|| DEFAULT_ENTRY.to_string());
+
+        if !Syntax::is_identifier(&entry) {
+            errors.push(HakoManifestError::InvalidEntry { path: path.to_path_buf(), entry: entry.clone() });
+        }
+
+        let mut dependencies = Vec::new();
+
+        for (each_name, each_dependency) in raw.dependencies {
+            if !Syntax::is_identifier(&each_name) {
+                errors.push(HakoManifestError::InvalidDependencyName { path: path.to_path_buf(), name: each_name.clone() });
+            } else if each_name == raw.hako.name {
+                errors.push(HakoManifestError::SelfDependency { path: path.to_path_buf(), name: each_name.clone() });
+            }
+
+            dependencies.push(HakoDependency { name: each_name, path: directory.join(each_dependency.path) });
+        }
+
+        if errors.len() != 0 {
+            return Err(errors);
+        }
+
+        Ok(HakoManifest {
+            name: raw.hako.name,
+            version: version.unwrap(),
+            entry,
+            source: directory.join(raw.hako.source.unwrap_or_else(|| PathBuf::from(DEFAULT_SOURCE))),
+            dependencies,
+            options: HakoOptions { checked_arithmetic: raw.options.checked_arithmetic },
+        })
+    }
+}
@@ -1,8 +1,10 @@
 pub mod hirify;
 pub mod ir;
+pub mod resolve;
 pub mod type_check;
 
 use self::ir::{item::*, path::*};
+use crate::span::Span;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Hir {
@@ -10,18 +12,40 @@ pub struct Hir {
     pub items: Vec<HirPathIndexBinding<HirItem>>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct HirIdentifier(String);
+#[derive(Clone, Debug)]
+pub struct HirIdentifier(String, Span);
+
+impl HirIdentifier {
+    pub fn new(id: String, span: Span) -> HirIdentifier {
+        HirIdentifier(id, span)
+    }
+
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.1
+    }
+}
+
+// Identifiers are compared by name so that path lookups don't depend on where they are written.
+impl PartialEq for HirIdentifier {
+    fn eq(&self, other: &HirIdentifier) -> bool {
+        self.0 == other.0
+    }
+}
 
+// Span is left default; use `HirIdentifier::new()` to locate the identifier.
 impl From<&str> for HirIdentifier {
     fn from(value: &str) -> Self {
-        HirIdentifier(value.to_string())
+        HirIdentifier(value.to_string(), Span::default())
     }
 }
 
 impl From<String> for HirIdentifier {
     fn from(value: String) -> Self {
-        HirIdentifier(value)
+        HirIdentifier(value, Span::default())
     }
 }
 
@@ -1,17 +1,23 @@
 use volt::tree::*;
-use crate::ast::operator::OperationParser;
+use crate::ast::syntax::Syntax;
+use crate::ast::operator::{OperationParser, OperationParserError, table::{OperatorDefinition, OperatorFixity}};
+use crate::span::{FileId, SourcePosition, Span};
 use super::*;
 use super::ir::{expr::*, item::*, path::*};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct AstHako<'a> {
     pub id: String,
+    // Ids of the hakos which the hako can refer to.
+    pub dependencies: Vec<String>,
     pub modules: Vec<AstModule<'a>>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct AstModule<'a> {
     pub id: String,
+    // Spans in the module refer to the file.
+    pub file_id: FileId,
     pub node: &'a SyntaxNode,
     pub submodules: Vec<AstModule<'a>>,
 }
@@ -23,18 +29,27 @@ pub enum TreeHirifierLog {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum TreeHirifierError {
-    PathSegmentMustLocateFirstPosition { path_segment: String },
-    SelfArgumentMustLocateFirstPosition,
+    PathSegmentMustLocateFirstPosition { path_segment: String, span: Span },
+    SelfArgumentMustLocateFirstPosition { span: Span },
+    InvalidOperation { error: OperationParserError, span: Span },
+    // Name is the one of error node in syntax tree.
+    // Source is the text of the error node which fixes are suggested from.
+    InvalidSyntax { name: String, source: String, span: Span },
+    // Submodules and items in a module share names. Previous is the span of the former definition.
+    DuplicateDefinition { name: String, previous: Span, span: Span },
+    // Imported name is also defined in the module. Declaration is the span of the definition.
+    ImportConflict { name: String, declaration: Span, span: Span },
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ItemHirifierResult {
     ItemPathIndex(HirPathIndex),
-    UseDeclaration(HirPath),
+    UseDeclaration(Vec<HirUseDeclaration>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct TreeHirifier {
+    file_id: FileId,
     path_index_generator: HirPathIndexGenerator,
     pub(crate) path_tree: HirPathTree,
     pub(crate) items: Vec<HirPathIndexBinding<HirItem>>,
@@ -44,6 +59,7 @@ pub struct TreeHirifier {
 impl TreeHirifier {
     pub fn new() -> TreeHirifier {
         TreeHirifier {
+            file_id: FileId::default(),
             path_index_generator: HirPathIndexGenerator::new(),
             path_tree: HirPathTree::new(),
             items: Vec::new(),
@@ -54,8 +70,11 @@ impl TreeHirifier {
     pub fn hirify(hakos: Vec<&AstHako>) -> (Hir, Vec<TreeHirifierLog>) {
         let mut analyzer = TreeHirifier::new();
 
-        for each_hako in &hakos {
-            analyzer.hako(each_hako);
+        let hako_indexes: Vec<HirPathIndex> = hakos.iter().map(|v| analyzer.hako(v)).collect();
+
+        // Dependencies are resolved after all hakos are registered.
+        for (each_hako, each_index) in hakos.iter().zip(hako_indexes) {
+            analyzer.dependencies(each_hako, each_index);
         }
 
         (
@@ -67,8 +86,9 @@ impl TreeHirifier {
         )
     }
 
-    pub fn hako(&mut self, hako: &AstHako) {
+    pub fn hako(&mut self, hako: &AstHako) -> HirPathIndex {
         let path_index = self.path_index_generator.generate();
+        let file_id = hako.modules.first().map(|v| v.file_id).unwrap_or_default();
         let mut child_path_indexes = Vec::new();
 
         for each_module in &hako.modules {
@@ -81,9 +101,25 @@ impl TreeHirifier {
             kind: HirPathKind::Hako,
             parent: None,
             children: child_path_indexes,
+            // Hako has no source node, so that it is located at the start of its first module.
+            span: Span::new(file_id, SourcePosition::default(), SourcePosition::default()),
         };
 
         self.path_tree.add_node(&mut self.path_index_generator, Some(path_index), path_node);
+        path_index
+    }
+
+    // Unknown hakos are ignored since dependencies are verified when they are loaded.
+    pub fn dependencies(&mut self, hako: &AstHako, path_index: HirPathIndex) {
+        for each_dependency in &hako.dependencies {
+            let segment = HirPathSegment::from(each_dependency.clone());
+
+            let dependency_index = self.path_tree.find_hako(&segment).map(|(v, _)| *v);
+
+            if let Some(dependency_index) = dependency_index {
+                self.path_tree.add_dependency(path_index, dependency_index);
+            }
+        }
     }
 
     pub fn module(&mut self, module: &AstModule, parent: HirPathIndex) -> HirPathIndex {
@@ -93,27 +129,94 @@ impl TreeHirifier {
 
         let mut submodules: Vec<HirPathIndex> = module.submodules.iter().map(|v| self.module(v, path_index)).collect();
         children.append(&mut submodules);
+        // Submodules have their own files, so that the file of the caller is restored at the end.
+        let parent_file_id = self.file_id;
+        self.file_id = module.file_id;
 
         for each_subitem_node in module.node.children.filter_nodes() {
+            // Skips items which failed to be parsed.
+            let item_content_node = TreeHirifier::item_content(each_subitem_node);
+
+            if Syntax::is_error_node(item_content_node) {
+                self.log_syntax_errors(item_content_node);
+                continue;
+            }
+
             match self.item(each_subitem_node, path_index) {
                 ItemHirifierResult::ItemPathIndex(path_index) => children.push(path_index),
-                ItemHirifierResult::UseDeclaration(path) => use_declarations.push(path),
+                ItemHirifierResult::UseDeclaration(mut declarations) => use_declarations.append(&mut declarations),
             }
         }
 
+        let children = self.unique_children(children);
+        self.import_conflicts(&children, &use_declarations);
+
         let path_node = HirPathNode {
             id: module.id.clone().into(),
             kind: HirPathKind::Module { use_declarations },
             parent: Some(parent),
             children,
+            span: self.span(module.node),
         };
 
         self.path_tree.add_node(&mut self.path_index_generator, Some(path_index), path_node);
+        self.file_id = parent_file_id;
         path_index
     }
 
-    pub fn identifier(&mut self, node: &SyntaxNode) -> String {
-        node.children.get_leaf(0).value.clone()
+    // Children which have the same id as a former child are reported and excluded so that names are found unambiguously.
+    pub fn unique_children(&mut self, children: Vec<HirPathIndex>) -> Vec<HirPathIndex> {
+        let mut unique_children = Vec::new();
+
+        for each_child in children {
+            let child_node = match self.path_tree.get(&each_child) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let error = self.path_tree.find_child(&unique_children, &child_node.id).map(|(_, previous_node)| TreeHirifierError::DuplicateDefinition {
+                name: child_node.id.id().to_string(),
+                previous: previous_node.span.clone(),
+                span: child_node.span.clone(),
+            });
+
+            match error {
+                // Rejected definitions are neither checked nor generated.
+                Some(error) => {
+                    self.logs.push(TreeHirifierLog::Error(error));
+                    let removed_indexes = self.path_tree.remove(&each_child);
+                    self.items.retain(|v| !removed_indexes.contains(v.index()));
+                },
+                None => unique_children.push(each_child),
+            }
+        }
+
+        unique_children
+    }
+
+    // Glob imports are not reported since they are shadowed by the children.
+    pub fn import_conflicts(&mut self, children: &Vec<HirPathIndex>, use_declarations: &Vec<HirUseDeclaration>) {
+        for each_declaration in use_declarations {
+            let name = match &each_declaration.kind {
+                HirUseKind::Named(name) => name,
+                HirUseKind::Glob => continue,
+            };
+
+            if let Some((_, child_node)) = self.path_tree.find_child(children, name) {
+                let error = TreeHirifierError::ImportConflict {
+                    name: name.id().to_string(),
+                    declaration: child_node.span.clone(),
+                    span: each_declaration.span.clone(),
+                };
+
+                self.logs.push(TreeHirifierLog::Error(error));
+            }
+        }
+    }
+
+    pub fn identifier(&mut self, node: &SyntaxNode) -> HirIdentifier {
+        let leaf = node.children.get_leaf(0);
+        HirIdentifier::new(leaf.value.clone(), self.leaf_span(leaf))
     }
 
     pub fn accessibility(&mut self, node: &SyntaxNode) -> HirAccessibility {
@@ -130,7 +233,8 @@ impl TreeHirifier {
 
     pub fn item(&mut self, node: &SyntaxNode, parent: HirPathIndex) -> ItemHirifierResult {
         let path_index = self.path_index_generator.generate();
-        let content = node.children.get_node(0);
+        let content = TreeHirifier::item_content(node);
+        let documentation = node.children.find_node_or_none("Item::documentation").map(|v| self.documentation(v));
 
         let (path_node, item) = match content.name.as_str() {
             "UseDeclaration::use_declaration" => return ItemHirifierResult::UseDeclaration(self.use_declaration(content)),
@@ -138,13 +242,14 @@ impl TreeHirifier {
                 let (id, function) = self.function(content);
 
                 let path_node = HirPathNode {
-                    id: id.clone().into(),
-                    kind: HirPathKind::Function,
+                    id,
+                    kind: HirPathKind::Function { accessibility: function.accessibility.clone() },
                     parent: Some(parent),
                     children: Vec::new(),
+                    span: self.span(content),
                 };
 
-                (path_node, HirItem::Function(function))
+                (path_node, HirItem::new(HirItemKind::Function(function), self.span(content)).with_documentation(documentation))
             },
             _ => unreachable!("unknown item content name"),
         };
@@ -155,28 +260,79 @@ impl TreeHirifier {
         ItemHirifierResult::ItemPathIndex(path_index)
     }
 
-    pub fn use_declaration(&mut self, node: &SyntaxNode) -> HirPath {
-        let mut segments = Vec::new();
+    // Documentation is optional and precedes the content.
+    fn item_content(node: &SyntaxNode) -> &SyntaxNode {
+        node.children.filter_nodes().into_iter().find(|v| v.name != "Item::documentation").expect("item must have a content")
+    }
 
-        for (index, each_identifier) in node.children.filter_leaves().iter().enumerate() {
-            let new_segment = each_identifier.value.clone();
+    // Removes "///" and a following space from each line.
+    pub fn documentation(&mut self, node: &SyntaxNode) -> String {
+        let lines: Vec<String> = node.children.filter_leaves().iter().map(|v| {
+            let line = v.value.trim_start_matches("///");
+            line.strip_prefix(' ').unwrap_or(line).to_string()
+        }).collect();
 
-            if index != 0 {
-                match new_segment.as_str() {
-                    "hako" | "self" => self.logs.push(
-                        TreeHirifierLog::Error(TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment: new_segment.to_string() }),
-                    ),
-                    _ => (),
-                }
-            }
+        lines.join("\n")
+    }
 
-            segments.push(new_segment.into());
+    pub fn use_declaration(&mut self, node: &SyntaxNode) -> Vec<HirUseDeclaration> {
+        let mut declarations = Vec::new();
+        self.use_tree(node, Vec::new(), &mut declarations);
+        declarations
+    }
+
+    // Segments of the enclosing trees are prepended to the path of each tree in a group.
+    pub fn use_tree(&mut self, node: &SyntaxNode, mut segments: Vec<HirPathSegment>, declarations: &mut Vec<HirUseDeclaration>) {
+        let mut alias = None;
+
+        for each_child in &node.children {
+            match each_child {
+                SyntaxChild::Leaf(leaf) => {
+                    let new_segment = leaf.value.clone();
+                    let span = self.leaf_span(leaf);
+
+                    // `super` can follow `self` and other `super` like `self::super::super`.
+                    let misplaced = match new_segment.as_str() {
+                        "hako" | "self" => segments.len() != 0,
+                        "super" => segments.iter().any(|v| v.id() != "self" && v.id() != "super"),
+                        _ => false,
+                    };
+
+                    if misplaced {
+                        self.logs.push(
+                            TreeHirifierLog::Error(TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment: new_segment.to_string(), span: span.clone() }),
+                        );
+                    }
+
+                    segments.push(HirIdentifier::new(new_segment, span));
+                },
+                SyntaxChild::Node(child) => match child.name.as_str() {
+                    "UseDeclaration::group" => {
+                        for each_tree in child.children.filter_nodes() {
+                            self.use_tree(each_tree, segments.clone(), declarations);
+                        }
+
+                        return;
+                    },
+                    "UseDeclaration::glob" => {
+                        declarations.push(HirUseDeclaration::new(HirPath::Unresolved(segments), HirUseKind::Glob, self.span(node)));
+                        return;
+                    },
+                    "UseDeclaration::alias" => alias = Some(self.identifier(child)),
+                    _ => unreachable!("unknown use tree content name"),
+                },
+            }
         }
 
-        HirPath::Unresolved(segments)
+        let name = match alias.or_else(|| segments.last().cloned()) {
+            Some(v) => v,
+            None => return,
+        };
+
+        declarations.push(HirUseDeclaration::new(HirPath::Unresolved(segments), HirUseKind::Named(name), self.span(node)));
     }
 
-    pub fn function(&mut self, node: &SyntaxNode) -> (String, HirFunction) {
+    pub fn function(&mut self, node: &SyntaxNode) -> (HirIdentifier, HirFunction) {
         let id = self.identifier(&node.children.find_node("Identifier::identifier"));
         let accessibility = self.accessibility(node.children.find_node("Main::accessibility"));
 
@@ -188,8 +344,15 @@ impl TreeHirifier {
         let arguments = node.children.find_node("args").children.filter_nodes().iter().enumerate()
             .map(|(i, v)| self.formal_argument(i, v)).collect();
 
-        let expressions = node.children.find_node("exprs").children.filter_nodes().iter()
-            .map(|v| self.expression(v)).collect();
+        let mut expressions = Vec::new();
+
+        for each_expression_node in node.children.find_node("exprs").children.filter_nodes() {
+            if Syntax::is_error_node(each_expression_node) {
+                self.log_syntax_errors(each_expression_node);
+            } else {
+                expressions.push(self.expression(each_expression_node));
+            }
+        }
 
         (id, HirFunction { accessibility, return_type, arguments, expressions })
     }
@@ -199,10 +362,10 @@ impl TreeHirifier {
             (self.identifier(id_node), self.data_type(node.children.find_node("DataType::data_type")))
         } else if node.children.has_leaf("self") {
             if index != 0 {
-                self.logs.push(TreeHirifierLog::Error(TreeHirifierError::SelfArgumentMustLocateFirstPosition));
+                self.logs.push(TreeHirifierLog::Error(TreeHirifierError::SelfArgumentMustLocateFirstPosition { span: self.span(node) }));
             }
 
-            ("self".to_string(), HirDataType::Primitive(HirPrimitiveDataType::SelfType))
+            (HirIdentifier::new("self".to_string(), self.span(node)), HirDataType::Primitive(HirPrimitiveDataType::SelfType))
         } else {
             unreachable!("formal argument must have an identifier or self keyword");
         };
@@ -213,61 +376,90 @@ impl TreeHirifier {
             HirMutability::Immutable
         };
 
-        HirIdentifierBinding::new(id.into(), HirFormalArgument { mutability, data_type })
+        HirIdentifierBinding::new(id, HirFormalArgument { mutability, data_type })
     }
 
+    // Syntax errors are collected only here so that nested expressions are not searched again.
     pub fn expression(&mut self, node: &SyntaxNode) -> HirExpression {
+        if self.log_syntax_errors(node) {
+            return HirExpression::new(HirExpressionKind::Error, self.span(node));
+        }
+
+        self.valid_expression(node)
+    }
+
+    // Node must not contain error nodes.
+    fn valid_expression(&mut self, node: &SyntaxNode) -> HirExpression {
         let content_node = node.children.get_node(0);
 
-        match content_node.name.as_str() {
-            "Operation::operation" => self.operation(content_node),
-            "Literal::literal" => HirExpression::Literal(self.literal(content_node)),
-            "Identifier::identifier" => HirExpression::Identifier(self.identifier(content_node).into()),
-            "DataType::data_type" => HirExpression::DataType(self.data_type(content_node)),
+        let kind = match content_node.name.as_str() {
+            "Operation::operation" => return self.operation(content_node),
+            "Literal::literal" => HirExpressionKind::Literal(self.literal(content_node)),
+            "Identifier::identifier" | "Expression::path_root" => HirExpressionKind::Identifier(self.identifier(content_node)),
+            "DataType::data_type" => HirExpressionKind::DataType(self.data_type(content_node)),
             _ => unreachable!("unknown expression"),
-        }
+        };
+
+        HirExpression::new(kind, self.span(node))
     }
 
     pub fn operation(&mut self, node: &SyntaxNode) -> HirExpression {
-        let tokens = node.children.iter().map(|each_child| self.operation_token(each_child.into_node())).collect();
+        let token_nodes: Vec<&SyntaxNode> = node.children.iter().map(|each_child| each_child.into_node()).collect();
+        let tokens = token_nodes.iter().map(|each_node| self.operation_token(each_node)).collect();
 
         match OperationParser::parse(tokens) {
-            Ok(v) => v,
-            Err(e) => unimplemented!("{:?}", e),
+            Ok(mut v) => {
+                // Expands the span to cover operators on both sides.
+                v.span = self.span(node);
+                v
+            },
+            Err(e) => {
+                let span = match token_nodes.get(e.token_index()) {
+                    Some(token_node) => self.span(token_node),
+                    None => self.span(node),
+                };
+
+                self.logs.push(TreeHirifierLog::Error(TreeHirifierError::InvalidOperation { error: e, span }));
+                // Continues hirifying with the placeholder.
+                HirExpression::new(HirExpressionKind::Error, self.span(node))
+            },
         }
     }
 
     pub fn operation_token(&mut self, node: &SyntaxNode) -> HirOperationToken {
         match node.name.as_ref() {
             "operator" => HirOperationToken::Operator(self.operator(node)),
-            _ => HirOperationToken::Term(self.expression(node))
+            _ => HirOperationToken::Term(self.valid_expression(node))
         }
     }
 
     pub fn operator(&mut self, node: &SyntaxNode) -> HirOperator {
         if let Some(operator_leaf) = node.children.get_leaf_or_none(0) {
-            match operator_leaf.value.as_ref() {
-                "=" => HirOperator::Substitute,
-                "+" => HirOperator::Add,
-                "-" => HirOperator::Subtract,
-                "*" => HirOperator::Multiply,
-                "!e" => HirOperator::Not,
-                "~e" => HirOperator::BitNot,
-                "-e" => HirOperator::Negative,
-                "e!" => HirOperator::Nonnize,
-                "e?" => HirOperator::Propagate,
-                "." => HirOperator::MemberAccess,
-                "::" => HirOperator::Path,
-                "(" => HirOperator::GroupBegin,
-                ")" => HirOperator::GroupEnd,
-                _ => todo!("add more operators"),
+            let value = operator_leaf.value.as_str();
+
+            // Prefix and postfix operators are marked with "e" which stands for the term like "-e" and "e!".
+            let (symbol, fixity) = match value {
+                "(" => return HirOperator::GroupBegin,
+                ")" => return HirOperator::GroupEnd,
+                _ => if let Some(symbol) = value.strip_suffix('e') {
+                    (symbol, OperatorFixity::Prefix)
+                } else if let Some(symbol) = value.strip_prefix('e') {
+                    (symbol, OperatorFixity::Postfix)
+                } else {
+                    (value, OperatorFixity::Infix)
+                },
+            };
+
+            match OperatorDefinition::find_by_symbol(symbol, fixity) {
+                Some(definition) => definition.operator.clone(),
+                None => unreachable!("unknown operator"),
             }
         } else {
             let operator_node = node.children.get_node(0);
 
             match operator_node.name.as_str() {
                 "Operation::function_call_operator" => {
-                    let arguments = operator_node.children.filter_nodes().iter().map(|v| self.expression(v)).collect();
+                    let arguments = operator_node.children.filter_nodes().iter().map(|v| self.valid_expression(v)).collect();
                     HirOperator::FunctionCall(arguments)
                 },
                 _ => unreachable!("unknown format of operator node"),
@@ -275,6 +467,53 @@ impl TreeHirifier {
         }
     }
 
+    // Logs every error node in the node and returns whether any error node exists.
+    pub fn log_syntax_errors(&mut self, node: &SyntaxNode) -> bool {
+        let error_nodes = Syntax::collect_errors(node);
+
+        for each_error_node in &error_nodes {
+            let span = self.span(each_error_node);
+            let source = self.source(each_error_node);
+            self.logs.push(TreeHirifierLog::Error(TreeHirifierError::InvalidSyntax { name: each_error_node.name.clone(), source, span }));
+        }
+
+        error_nodes.len() != 0
+    }
+
+    // Covers from the first leaf to the end of the last leaf in the node.
+    pub fn span(&self, node: &SyntaxNode) -> Span {
+        let mut leaves = Vec::new();
+        TreeHirifier::collect_leaves(node, &mut leaves);
+
+        match (leaves.first(), leaves.last()) {
+            (Some(first_leaf), Some(last_leaf)) => self.leaf_span(first_leaf).join(&self.leaf_span(last_leaf)),
+            _ => Span::new(self.file_id, SourcePosition::default(), SourcePosition::default()),
+        }
+    }
+
+    // Recovery leaves like `invalid_item` can span multiple lines.
+    pub fn leaf_span(&self, leaf: &SyntaxLeaf) -> Span {
+        let start = SourcePosition::new(leaf.start.index, leaf.start.line, leaf.start.column);
+        let end = start.advance_by(&leaf.value);
+        Span::new(self.file_id, start, end)
+    }
+
+    // Hidden elements like whitespace are not included.
+    fn source(&self, node: &SyntaxNode) -> String {
+        let mut leaves = Vec::new();
+        TreeHirifier::collect_leaves(node, &mut leaves);
+        leaves.iter().map(|v| v.value.as_str()).collect()
+    }
+
+    fn collect_leaves<'a>(node: &'a SyntaxNode, leaves: &mut Vec<&'a SyntaxLeaf>) {
+        for each_child in &node.children {
+            match each_child {
+                SyntaxChild::Node(v) => TreeHirifier::collect_leaves(v, leaves),
+                SyntaxChild::Leaf(v) => leaves.push(v),
+            }
+        }
+    }
+
     pub fn literal(&mut self, node: &SyntaxNode) -> HirLiteral {
         let content = node.children.get_node(0);
 
@@ -344,9 +583,9 @@ impl TreeHirifier {
         match content.name.as_str() {
             "DataType::primitive" => HirDataType::Primitive(self.primitive_data_type(content)),
             "DataType::generic" => {
-                let id = content.children.find_node("Identifier::identifier").children.get_leaf(0).value.clone();
+                let id = self.identifier(content.children.find_node("Identifier::identifier"));
                 let arguments = self.generic_arguments(&content.children.find_node("DataType::generic_arguments"));
-                HirDataType::Generic(HirIdentifierBinding::new(id.into(), HirGenericDataType { arguments }))
+                HirDataType::Generic(HirIdentifierBinding::new(id, HirGenericDataType { arguments }))
             },
             _ => unreachable!("unknown data type"),
         }
@@ -379,7 +618,7 @@ impl TreeHirifier {
         node.children.filter_nodes().iter().map(|data_type_node| {
             match data_type_node.name.as_str() {
                 // fix: replace identifier to path
-                "Identifier::identifier" => HirDataType::Identifier(data_type_node.children.get_leaf(0).value.clone().into()),
+                "Identifier::identifier" => HirDataType::Identifier(self.identifier(data_type_node)),
                 "DataType::data_type" => self.data_type(data_type_node),
                 _ => unreachable!("unknown argument format in generic data type"),
             }
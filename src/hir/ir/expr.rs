@@ -1,11 +1,33 @@
 use crate::hir::*;
+use crate::span::Span;
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum HirExpression {
+pub struct HirExpression {
+    pub kind: HirExpressionKind,
+    pub span: Span,
+}
+
+impl HirExpression {
+    pub fn new(kind: HirExpressionKind, span: Span) -> HirExpression {
+        HirExpression { kind, span }
+    }
+}
+
+// Span is left default; use `HirExpression::new()` to locate the expression.
+impl From<HirExpressionKind> for HirExpression {
+    fn from(value: HirExpressionKind) -> Self {
+        HirExpression::new(value, Span::default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HirExpressionKind {
     Operation(Box<HirOperation>),
     Literal(HirLiteral),
     Identifier(HirIdentifier),
     DataType(HirDataType),
+    // Placeholder of the expression which failed to be hirified.
+    Error,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -52,6 +74,26 @@ pub enum HirOperation {
     Add(HirExpression, HirExpression),
     Subtract(HirExpression, HirExpression),
     Multiply(HirExpression, HirExpression),
+    Divide(HirExpression, HirExpression),
+    Remainder(HirExpression, HirExpression),
+    AddSubstitute(HirExpression, HirExpression),
+    SubtractSubstitute(HirExpression, HirExpression),
+    MultiplySubstitute(HirExpression, HirExpression),
+    DivideSubstitute(HirExpression, HirExpression),
+    RemainderSubstitute(HirExpression, HirExpression),
+    Equal(HirExpression, HirExpression),
+    NotEqual(HirExpression, HirExpression),
+    LessThan(HirExpression, HirExpression),
+    LessThanOrEqual(HirExpression, HirExpression),
+    GreaterThan(HirExpression, HirExpression),
+    GreaterThanOrEqual(HirExpression, HirExpression),
+    And(HirExpression, HirExpression),
+    Or(HirExpression, HirExpression),
+    BitAnd(HirExpression, HirExpression),
+    BitOr(HirExpression, HirExpression),
+    BitXor(HirExpression, HirExpression),
+    LeftShift(HirExpression, HirExpression),
+    RightShift(HirExpression, HirExpression),
     Not(HirExpression),
     BitNot(HirExpression),
     Negative(HirExpression),
@@ -63,6 +105,131 @@ pub enum HirOperation {
     Group(HirExpression),
 }
 
+impl HirOperation {
+    // Returns None for operations which are not constructed from an operator of the operator table.
+    pub fn operator(&self) -> Option<HirOperator> {
+        let operator = match self {
+            HirOperation::Substitute(_, _) => HirOperator::Substitute,
+            HirOperation::Add(_, _) => HirOperator::Add,
+            HirOperation::Subtract(_, _) => HirOperator::Subtract,
+            HirOperation::Multiply(_, _) => HirOperator::Multiply,
+            HirOperation::Divide(_, _) => HirOperator::Divide,
+            HirOperation::Remainder(_, _) => HirOperator::Remainder,
+            HirOperation::AddSubstitute(_, _) => HirOperator::AddSubstitute,
+            HirOperation::SubtractSubstitute(_, _) => HirOperator::SubtractSubstitute,
+            HirOperation::MultiplySubstitute(_, _) => HirOperator::MultiplySubstitute,
+            HirOperation::DivideSubstitute(_, _) => HirOperator::DivideSubstitute,
+            HirOperation::RemainderSubstitute(_, _) => HirOperator::RemainderSubstitute,
+            HirOperation::Equal(_, _) => HirOperator::Equal,
+            HirOperation::NotEqual(_, _) => HirOperator::NotEqual,
+            HirOperation::LessThan(_, _) => HirOperator::LessThan,
+            HirOperation::LessThanOrEqual(_, _) => HirOperator::LessThanOrEqual,
+            HirOperation::GreaterThan(_, _) => HirOperator::GreaterThan,
+            HirOperation::GreaterThanOrEqual(_, _) => HirOperator::GreaterThanOrEqual,
+            HirOperation::And(_, _) => HirOperator::And,
+            HirOperation::Or(_, _) => HirOperator::Or,
+            HirOperation::BitAnd(_, _) => HirOperator::BitAnd,
+            HirOperation::BitOr(_, _) => HirOperator::BitOr,
+            HirOperation::BitXor(_, _) => HirOperator::BitXor,
+            HirOperation::LeftShift(_, _) => HirOperator::LeftShift,
+            HirOperation::RightShift(_, _) => HirOperator::RightShift,
+            HirOperation::MemberAccess(_, _) => HirOperator::MemberAccess,
+            HirOperation::Not(_) => HirOperator::Not,
+            HirOperation::BitNot(_) => HirOperator::BitNot,
+            HirOperation::Negative(_) => HirOperator::Negative,
+            HirOperation::Nonnize(_) => HirOperator::Nonnize,
+            HirOperation::Propagate(_) => HirOperator::Propagate,
+            HirOperation::FunctionCall(_, _) | HirOperation::Path(_) | HirOperation::Group(_) => return None,
+        };
+
+        Some(operator)
+    }
+
+    pub fn terms(&self) -> Vec<&HirExpression> {
+        match self {
+            HirOperation::Substitute(left, right) => vec![left, right],
+            HirOperation::Add(left, right) => vec![left, right],
+            HirOperation::Subtract(left, right) => vec![left, right],
+            HirOperation::Multiply(left, right) => vec![left, right],
+            HirOperation::Divide(left, right) => vec![left, right],
+            HirOperation::Remainder(left, right) => vec![left, right],
+            HirOperation::AddSubstitute(left, right) => vec![left, right],
+            HirOperation::SubtractSubstitute(left, right) => vec![left, right],
+            HirOperation::MultiplySubstitute(left, right) => vec![left, right],
+            HirOperation::DivideSubstitute(left, right) => vec![left, right],
+            HirOperation::RemainderSubstitute(left, right) => vec![left, right],
+            HirOperation::Equal(left, right) => vec![left, right],
+            HirOperation::NotEqual(left, right) => vec![left, right],
+            HirOperation::LessThan(left, right) => vec![left, right],
+            HirOperation::LessThanOrEqual(left, right) => vec![left, right],
+            HirOperation::GreaterThan(left, right) => vec![left, right],
+            HirOperation::GreaterThanOrEqual(left, right) => vec![left, right],
+            HirOperation::And(left, right) => vec![left, right],
+            HirOperation::Or(left, right) => vec![left, right],
+            HirOperation::BitAnd(left, right) => vec![left, right],
+            HirOperation::BitOr(left, right) => vec![left, right],
+            HirOperation::BitXor(left, right) => vec![left, right],
+            HirOperation::LeftShift(left, right) => vec![left, right],
+            HirOperation::RightShift(left, right) => vec![left, right],
+            HirOperation::MemberAccess(left, right) => vec![left, right],
+            HirOperation::Not(term) => vec![term],
+            HirOperation::BitNot(term) => vec![term],
+            HirOperation::Negative(term) => vec![term],
+            HirOperation::Nonnize(term) => vec![term],
+            HirOperation::Propagate(term) => vec![term],
+            HirOperation::FunctionCall(term, arguments) => {
+                let mut terms = vec![term];
+                terms.extend(arguments.iter());
+                terms
+            },
+            HirOperation::Path(_) => Vec::new(),
+            HirOperation::Group(term) => vec![term],
+        }
+    }
+
+    pub fn terms_mut(&mut self) -> Vec<&mut HirExpression> {
+        match self {
+            HirOperation::Substitute(left, right) => vec![left, right],
+            HirOperation::Add(left, right) => vec![left, right],
+            HirOperation::Subtract(left, right) => vec![left, right],
+            HirOperation::Multiply(left, right) => vec![left, right],
+            HirOperation::Divide(left, right) => vec![left, right],
+            HirOperation::Remainder(left, right) => vec![left, right],
+            HirOperation::AddSubstitute(left, right) => vec![left, right],
+            HirOperation::SubtractSubstitute(left, right) => vec![left, right],
+            HirOperation::MultiplySubstitute(left, right) => vec![left, right],
+            HirOperation::DivideSubstitute(left, right) => vec![left, right],
+            HirOperation::RemainderSubstitute(left, right) => vec![left, right],
+            HirOperation::Equal(left, right) => vec![left, right],
+            HirOperation::NotEqual(left, right) => vec![left, right],
+            HirOperation::LessThan(left, right) => vec![left, right],
+            HirOperation::LessThanOrEqual(left, right) => vec![left, right],
+            HirOperation::GreaterThan(left, right) => vec![left, right],
+            HirOperation::GreaterThanOrEqual(left, right) => vec![left, right],
+            HirOperation::And(left, right) => vec![left, right],
+            HirOperation::Or(left, right) => vec![left, right],
+            HirOperation::BitAnd(left, right) => vec![left, right],
+            HirOperation::BitOr(left, right) => vec![left, right],
+            HirOperation::BitXor(left, right) => vec![left, right],
+            HirOperation::LeftShift(left, right) => vec![left, right],
+            HirOperation::RightShift(left, right) => vec![left, right],
+            HirOperation::MemberAccess(left, right) => vec![left, right],
+            HirOperation::Not(term) => vec![term],
+            HirOperation::BitNot(term) => vec![term],
+            HirOperation::Negative(term) => vec![term],
+            HirOperation::Nonnize(term) => vec![term],
+            HirOperation::Propagate(term) => vec![term],
+            HirOperation::FunctionCall(term, arguments) => {
+                let mut terms = vec![term];
+                terms.extend(arguments.iter_mut());
+                terms
+            },
+            HirOperation::Path(_) => Vec::new(),
+            HirOperation::Group(term) => vec![term],
+        }
+    }
+}
+
 pub type HirOperationSequence = Vec<HirOperationToken>;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -77,6 +244,26 @@ pub enum HirOperator {
     Add,
     Subtract,
     Multiply,
+    Divide,
+    Remainder,
+    AddSubstitute,
+    SubtractSubstitute,
+    MultiplySubstitute,
+    DivideSubstitute,
+    RemainderSubstitute,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    LeftShift,
+    RightShift,
     Not,
     BitNot,
     Negative,
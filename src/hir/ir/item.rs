@@ -1,8 +1,34 @@
 use super::expr::*;
 use crate::hir::*;
+use crate::span::Span;
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum HirItem {
+pub struct HirItem {
+    pub kind: HirItemKind,
+    pub span: Span,
+    // Lines of doc comments which are joined with newlines.
+    pub documentation: Option<String>,
+}
+
+impl HirItem {
+    pub fn new(kind: HirItemKind, span: Span) -> HirItem {
+        HirItem { kind, span, documentation: None }
+    }
+
+    pub fn with_documentation(mut self, documentation: Option<String>) -> HirItem {
+        self.documentation = documentation;
+        self
+    }
+}
+
+impl From<HirItemKind> for HirItem {
+    fn from(value: HirItemKind) -> Self {
+        HirItem::new(value, Span::default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HirItemKind {
     UseDeclaration(HirPath),
     Function(HirFunction),
     Struct,
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::collections::BTreeMap;
-use crate::hir::HirIdentifier;
+use crate::hir::{HirAccessibility, HirIdentifier};
+use crate::span::Span;
 
 // fix
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -71,6 +72,8 @@ impl HirPathIndexGenerator {
 #[derive(Clone, Debug, PartialEq)]
 pub struct HirPathTree {
     pub(crate) hako_indexes: Vec<HirPathIndex>,
+    // Hakos which each hako depends on. Hakos without dependencies have no entry.
+    pub(crate) dependencies: BTreeMap<HirPathIndex, Vec<HirPathIndex>>,
     pub(crate) nodes: BTreeMap<HirPathIndex, HirPathNode>,
 }
 
@@ -78,14 +81,43 @@ impl HirPathTree {
     pub fn new() -> HirPathTree {
         HirPathTree {
             hako_indexes: Vec::new(),
+            dependencies: BTreeMap::new(),
             nodes: BTreeMap::new(),
         }
     }
 
+    pub fn add_dependency(&mut self, hako: HirPathIndex, dependency: HirPathIndex) {
+        self.dependencies.entry(hako).or_default().push(dependency);
+    }
+
+    // Hako can see itself and the hakos it depends on.
+    pub fn is_visible_hako(&self, from: &HirPathIndex, hako: &HirPathIndex) -> bool {
+        from == hako || self.dependencies.get(from).is_some_and(|v| v.contains(hako))
+    }
+
+    // Hako which the node belongs to.
+    pub fn hako_of(&self, index: &HirPathIndex) -> Option<HirPathIndex> {
+        let mut current_index = index.clone();
+
+        loop {
+            let node = self.get(&current_index)?;
+
+            match &node.parent {
+                Some(parent) => current_index = parent.clone(),
+                None if node.kind == HirPathKind::Hako => return Some(current_index),
+                None => return None,
+            }
+        }
+    }
+
     pub fn get(&self, index: &HirPathIndex) -> Option<&HirPathNode> {
         self.nodes.get(index)
     }
 
+    pub fn get_mut(&mut self, index: &HirPathIndex) -> Option<&mut HirPathNode> {
+        self.nodes.get_mut(index)
+    }
+
     // Path index will be auto-generated when None specified.
     pub fn add_node(&mut self, index_generator: &mut HirPathIndexGenerator, index: Option<HirPathIndex>, node: HirPathNode) -> HirPathIndex {
         let node_index = if let Some(v) = index {
@@ -106,7 +138,20 @@ impl HirPathTree {
         node_index
     }
 
-    pub fn find<'a>(&'a self, path_segments: &'a Vec<HirPathSegment>) -> Option<(&'a HirPathIndex, &'a HirPathNode)> {
+    // Removes the node and its descendants. Returns the removed indexes.
+    pub fn remove(&mut self, index: &HirPathIndex) -> Vec<HirPathIndex> {
+        let mut removed_indexes = self.descendants(index);
+        removed_indexes.push(*index);
+
+        for each_index in &removed_indexes {
+            self.nodes.remove(each_index);
+        }
+
+        self.hako_indexes.retain(|v| !removed_indexes.contains(v));
+        removed_indexes
+    }
+
+    pub fn find<'a>(&'a self, path_segments: &'a [HirPathSegment]) -> Option<(&'a HirPathIndex, &'a HirPathNode)> {
         let mut path_segment_iter = path_segments.iter();
 
         let hako_node_pair = if let Some(hako_segment) = path_segment_iter.next() {
@@ -130,10 +175,178 @@ impl HirPathTree {
         Some(current_path_node)
     }
 
+    // Same as find() but hakos which the hako does not depend on are not found.
+    pub fn find_from<'a>(&'a self, from: &HirPathIndex, path_segments: &'a [HirPathSegment]) -> Option<(&'a HirPathIndex, &'a HirPathNode)> {
+        let (hako_index, _) = self.find_hako(path_segments.first()?)?;
+
+        if !self.is_visible_hako(from, hako_index) {
+            return None;
+        }
+
+        self.find(path_segments)
+    }
+
     pub fn find_hako<'a>(&'a self, segment: &'a HirPathSegment) -> Option<(&'a HirPathIndex, &'a HirPathNode)> {
         self.find_child(&self.hako_indexes, segment)
     }
 
+    // Looks up the name in the module. Items defined in the module come before imports.
+    pub fn find_in_scope(&self, module: &HirPathIndex, segment: &HirPathSegment) -> Option<HirPathIndex> {
+        let module_node = self.get(module)?;
+
+        match self.find_child(&module_node.children, segment) {
+            Some((index, _)) => Some(*index),
+            None => self.find_import(module, segment).map(|(_, index)| index),
+        }
+    }
+
+    // Returns the position of the use declaration in the module and the imported item.
+    // Named imports come before glob imports.
+    pub fn find_import(&self, module: &HirPathIndex, segment: &HirPathSegment) -> Option<(usize, HirPathIndex)> {
+        let use_declarations = match &self.get(module)?.kind {
+            HirPathKind::Module { use_declarations } => use_declarations,
+            _ => return None,
+        };
+
+        let named = use_declarations.iter().enumerate().find_map(|(position, v)| match (&v.path, &v.kind) {
+            (HirPath::Resolved(index), HirUseKind::Named(name)) if name == segment => Some((position, *index)),
+            _ => None,
+        });
+
+        named.or_else(|| use_declarations.iter().enumerate().find_map(|(position, v)| match (&v.path, &v.kind) {
+            // Inaccessible items are not imported by globs.
+            (HirPath::Resolved(index), HirUseKind::Glob) => self.find_child(&self.get(index)?.children, segment)
+                .filter(|(child_index, _)| self.is_accessible(module, child_index))
+                .map(|(child_index, _)| (position, *child_index)),
+            _ => None,
+        }))
+    }
+
+    // Paths which start with `hako`, `self` or `super` are resolved from the module.
+    pub fn is_relative(path_segments: &[HirPathSegment]) -> bool {
+        path_segments.first().is_some_and(|v| matches!(v.id(), "hako" | "self" | "super"))
+    }
+
+    // `hako` refers to the hako of the module, `self` to the module and each `super` to the parent.
+    pub fn find_relative(&self, module: &HirPathIndex, path_segments: &[HirPathSegment]) -> Option<HirPathIndex> {
+        let (mut current_index, mut rest_segments) = match path_segments.first()?.id() {
+            "hako" => (self.hako_of(module)?, &path_segments[1..]),
+            "self" => (*module, &path_segments[1..]),
+            "super" => (*module, path_segments),
+            _ => return None,
+        };
+
+        while let Some((first_segment, following_segments)) = rest_segments.split_first() {
+            if first_segment.id() != "super" {
+                break;
+            }
+
+            current_index = self.get(&current_index)?.parent?;
+            rest_segments = following_segments;
+        }
+
+        self.find_descendant(&current_index, rest_segments)
+    }
+
+    // Private items are accessible from the module subtree which they are defined in and `pub@hako` items from the same hako.
+    pub fn is_accessible(&self, from: &HirPathIndex, index: &HirPathIndex) -> bool {
+        let node = match self.get(index) {
+            Some(v) => v,
+            None => return false,
+        };
+
+        match node.accessibility() {
+            HirAccessibility::Public => true,
+            HirAccessibility::PublicInHako => self.hako_of(from) == self.hako_of(index),
+            HirAccessibility::Private => match &node.parent {
+                Some(parent) => self.is_ancestor(parent, from),
+                None => true,
+            },
+        }
+    }
+
+    // Node itself is regarded as its ancestor.
+    pub fn is_ancestor(&self, ancestor: &HirPathIndex, index: &HirPathIndex) -> bool {
+        let mut current_index = Some(*index);
+
+        while let Some(each_index) = current_index {
+            if each_index == *ancestor {
+                return true;
+            }
+
+            current_index = self.get(&each_index).and_then(|v| v.parent);
+        }
+
+        false
+    }
+
+    // Follows the segments from the node.
+    pub fn find_descendant(&self, index: &HirPathIndex, path_segments: &[HirPathSegment]) -> Option<HirPathIndex> {
+        let mut current_index = *index;
+
+        for each_segment in path_segments {
+            let (child_index, _) = self.find_child(&self.get(&current_index)?.children, each_segment)?;
+            current_index = *child_index;
+        }
+
+        Some(current_index)
+    }
+
+    // Ids from the hako to the node joined with `::` like `h::a::f`.
+    pub fn path_string(&self, index: &HirPathIndex) -> Option<String> {
+        let mut segments = Vec::new();
+        let mut current_index = Some(*index);
+
+        while let Some(each_index) = current_index {
+            let node = self.get(&each_index)?;
+            segments.push(node.id.id());
+            current_index = node.parent;
+        }
+
+        segments.reverse();
+        Some(segments.join("::"))
+    }
+
+    // Descendants in depth-first order. The node itself is excluded.
+    pub fn descendants(&self, index: &HirPathIndex) -> Vec<HirPathIndex> {
+        let mut descendants = Vec::new();
+
+        if let Some(node) = self.get(index) {
+            for each_child in &node.children {
+                descendants.push(*each_child);
+                descendants.append(&mut self.descendants(each_child));
+            }
+        }
+
+        descendants
+    }
+
+    // Nodes in order of index whose kind satisfies the predicate like `matches!(kind, HirPathKind::Function { .. })`.
+    pub fn filter_by_kind<F: Fn(&HirPathKind) -> bool>(&self, predicate: F) -> Vec<HirPathIndex> {
+        self.nodes.iter()
+            .filter(|(_, v)| predicate(&v.kind))
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    // Nearest module which contains the node. The node itself is excluded.
+    pub fn enclosing_module(&self, index: &HirPathIndex) -> Option<HirPathIndex> {
+        let mut current_index = self.get(index)?.parent;
+
+        while let Some(each_index) = current_index {
+            let node = self.get(&each_index)?;
+
+            if matches!(node.kind, HirPathKind::Module { .. }) {
+                return Some(each_index);
+            }
+
+            current_index = node.parent;
+        }
+
+        None
+    }
+
+    // Ids of the children are unique since the hirifier excludes duplicate definitions from modules.
     pub(crate) fn find_child<'a>(&'a self, indexes: &'a Vec<HirPathIndex>, segment: &'a HirPathSegment) -> Option<(&'a HirPathIndex, &'a HirPathNode)> {
         for each_index in indexes {
             if let Some(path_node) = self.get(each_index) {
@@ -153,13 +366,24 @@ pub struct HirPathNode {
     pub kind: HirPathKind,
     pub parent: Option<HirPathIndex>,
     pub children: Vec<HirPathIndex>,
+    pub span: Span,
+}
+
+impl HirPathNode {
+    // Hakos and modules are always public.
+    pub fn accessibility(&self) -> HirAccessibility {
+        match &self.kind {
+            HirPathKind::Function { accessibility } => accessibility.clone(),
+            _ => HirAccessibility::Public,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum HirPathKind {
     Hako,
-    Module { use_declarations: Vec<HirPath> },
-    Function,
+    Module { use_declarations: Vec<HirUseDeclaration> },
+    Function { accessibility: HirAccessibility },
     Struct,
     Enum,
     Trait,
@@ -172,3 +396,26 @@ pub enum HirPath {
 }
 
 pub type HirPathSegment = HirIdentifier;
+
+// Grouped declarations like `use a::{b, c}` are flattened into each path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HirUseDeclaration {
+    pub path: HirPath,
+    pub kind: HirUseKind,
+    pub span: Span,
+}
+
+impl HirUseDeclaration {
+    pub fn new(path: HirPath, kind: HirUseKind, span: Span) -> HirUseDeclaration {
+        HirUseDeclaration { path, kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HirUseKind {
+    // Name is the alias or the last segment of the path.
+    Named(HirIdentifier),
+    // Imports all children of the item.
+    Glob,
+}
+
@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use crate::hir::ir::path::*;
+use crate::span::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UseResolverError {
+    UnresolvedImport { path: String, span: Span },
+    // Different items are imported with the same name into a module.
+    AmbiguousImport { name: String, span: Span },
+    // Imported item is not accessible from the module. Name is the fully-qualified path and declaration is the span of the item.
+    PrivateItemAccess { name: String, declaration: Span, span: Span },
+}
+
+// Resolves use declarations of every module so that imported names are found in the module scope.
+#[derive(Debug)]
+pub struct UseResolver<'a> {
+    pub(crate) path_tree: &'a mut HirPathTree,
+    pub(crate) errors: Vec<UseResolverError>,
+}
+
+impl<'a> UseResolver<'a> {
+    pub(crate) fn new(path_tree: &'a mut HirPathTree) -> UseResolver<'a> {
+        UseResolver::<'a> {
+            path_tree,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn resolve(path_tree: &'a mut HirPathTree) -> Vec<UseResolverError> {
+        let module_indexes = path_tree.filter_by_kind(|v| matches!(v, HirPathKind::Module { .. }));
+
+        let mut resolver = UseResolver::new(path_tree);
+
+        for each_module in module_indexes {
+            resolver.module(&each_module);
+        }
+
+        resolver.errors
+    }
+
+    pub(crate) fn module(&mut self, module: &HirPathIndex) {
+        let (use_declarations, children) = match self.path_tree.get(module) {
+            Some(HirPathNode { kind: HirPathKind::Module { use_declarations }, children, .. }) => (use_declarations.clone(), children.clone()),
+            _ => return,
+        };
+
+        let hako = self.path_tree.hako_of(module);
+        // Imported names and the items which they are bound to.
+        let mut names: BTreeMap<String, HirPathIndex> = BTreeMap::new();
+        let mut resolved_declarations = Vec::new();
+
+        for mut each_declaration in use_declarations {
+            let segments = match &each_declaration.path {
+                HirPath::Resolved(_) => {
+                    resolved_declarations.push(each_declaration);
+                    continue;
+                },
+                HirPath::Unresolved(segments) => segments,
+            };
+
+            let found = if HirPathTree::is_relative(segments) {
+                self.path_tree.find_relative(module, segments)
+            } else {
+                match &hako {
+                    Some(hako) => self.path_tree.find_from(hako, segments),
+                    None => self.path_tree.find(segments),
+                }.map(|(index, _)| *index)
+            };
+
+            let path_index = match found {
+                Some(v) => v,
+                None => {
+                    let path = segments.iter().map(|v| v.id()).collect::<Vec<&str>>().join("::");
+                    self.errors.push(UseResolverError::UnresolvedImport { path, span: each_declaration.span.clone() });
+                    resolved_declarations.push(each_declaration);
+                    continue;
+                },
+            };
+
+            if !self.path_tree.is_accessible(module, &path_index) {
+                if let (Some(path_node), Some(name)) = (self.path_tree.get(&path_index), self.path_tree.path_string(&path_index)) {
+                    self.errors.push(UseResolverError::PrivateItemAccess { name, declaration: path_node.span.clone(), span: each_declaration.span.clone() });
+                }
+
+                resolved_declarations.push(each_declaration);
+                continue;
+            }
+
+            // Glob imports bind no name by themselves and are shadowed by other names.
+            if let HirUseKind::Named(name) = &each_declaration.kind {
+                let name = name.id().to_string();
+                let span = each_declaration.span.clone();
+
+                // Conflicting imports are reported by the hirifier and left unresolved so that the local item is used.
+                if self.path_tree.find_child(&children, &name.as_str().into()).is_some() {
+                    resolved_declarations.push(each_declaration);
+                    continue;
+                }
+
+                // Ambiguous imports are left unresolved so that the first one is used.
+                match names.get(&name) {
+                    Some(bound_index) if *bound_index != path_index => {
+                        self.errors.push(UseResolverError::AmbiguousImport { name, span });
+                        resolved_declarations.push(each_declaration);
+                        continue;
+                    },
+                    _ => {
+                        names.insert(name, path_index);
+                    },
+                }
+            }
+
+            each_declaration.path = HirPath::Resolved(path_index);
+            resolved_declarations.push(each_declaration);
+        }
+
+        if let Some(module_node) = self.path_tree.get_mut(module) {
+            module_node.kind = HirPathKind::Module { use_declarations: resolved_declarations };
+        }
+    }
+}
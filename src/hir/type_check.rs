@@ -1,38 +1,95 @@
+use std::collections::BTreeSet;
+use crate::hir::{HirIdentifier, HirIdentifierBinding};
 use crate::hir::ir::{path::*, item::*, expr::*};
+use crate::span::Span;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum DataTypeError {
-    UnknownIdentifier,
+    UnknownIdentifier { span: Span },
+    // Path refers to a hako which the current hako does not depend on.
+    UndeclaredDependency { hako: String, span: Span },
+    // Item is not accessible from the current module. Name is the fully-qualified path and declaration is the span of the item.
+    PrivateItemAccess { name: String, declaration: Span, span: Span },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DataTypeWarning {
+    // Glob imports are named `*`.
+    UnusedImport { name: String, span: Span },
 }
 
 #[derive(Debug)]
 pub struct DataTypeChecker<'a> {
     pub(crate) path_tree: &'a HirPathTree,
+    // Hako of the item being checked. Paths are not restricted if none.
+    pub(crate) current_hako: Option<HirPathIndex>,
+    // Module of the item being checked. Names in the module and its imports are not found if none.
+    pub(crate) current_module: Option<HirPathIndex>,
+    // Formal arguments of the function being checked.
+    pub(crate) arguments: Vec<HirIdentifier>,
+    // Modules and positions of use declarations which are referred to.
+    pub(crate) used_imports: BTreeSet<(HirPathIndex, usize)>,
     pub(crate) errors: Vec<DataTypeError>,
+    pub(crate) warnings: Vec<DataTypeWarning>,
 }
 
 impl<'a> DataTypeChecker<'a> {
     pub(crate) fn new(path_tree: &'a HirPathTree) -> DataTypeChecker<'a> {
         DataTypeChecker::<'a> {
             path_tree,
+            current_hako: None,
+            current_module: None,
+            arguments: Vec::new(),
+            used_imports: BTreeSet::new(),
             errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
-    pub fn check(path_tree: &'a HirPathTree, items: &mut Vec<HirPathIndexBinding<HirItem>>) -> Vec<DataTypeError> {
+    pub fn check(path_tree: &'a HirPathTree, items: &mut Vec<HirPathIndexBinding<HirItem>>) -> (Vec<DataTypeError>, Vec<DataTypeWarning>) {
         let mut checker = DataTypeChecker::new(path_tree);
 
         for each_item in items {
+            checker.current_hako = path_tree.hako_of(each_item.index());
+            checker.current_module = path_tree.enclosing_module(each_item.index());
             checker.item(each_item.value_mut());
         }
 
-        checker.errors
+        checker.unused_imports();
+        (checker.errors, checker.warnings)
+    }
+
+    // Reports resolved use declarations which no path or identifier refers to.
+    pub(crate) fn unused_imports(&mut self) {
+        let path_tree = self.path_tree;
+
+        for (each_index, each_node) in &path_tree.nodes {
+            let use_declarations = match &each_node.kind {
+                HirPathKind::Module { use_declarations } => use_declarations,
+                _ => continue,
+            };
+
+            for (position, each_declaration) in use_declarations.iter().enumerate() {
+                if !matches!(each_declaration.path, HirPath::Resolved(_)) || self.used_imports.contains(&(*each_index, position)) {
+                    continue;
+                }
+
+                let name = match &each_declaration.kind {
+                    HirUseKind::Named(name) => name.id().to_string(),
+                    HirUseKind::Glob => "*".to_string(),
+                };
+
+                self.warnings.push(DataTypeWarning::UnusedImport { name, span: each_declaration.span.clone() });
+            }
+        }
     }
 
     pub(crate) fn item(&mut self, item: &mut HirItem) {
         // todo: add PathBinding<HirItem>
-        match item {
-            HirItem::Function(function) => {
+        match &mut item.kind {
+            HirItemKind::Function(function) => {
+                self.arguments = function.arguments.iter().map(|v| v.identifier().clone()).collect();
+
                 // todo: check return type
                 for each_expr in &mut function.expressions {
                     self.expression(each_expr);
@@ -43,47 +100,93 @@ impl<'a> DataTypeChecker<'a> {
     }
 
     pub(crate) fn expression(&mut self, expr: &mut HirExpression) {
-        match expr {
+        match &mut expr.kind {
             // todo: 文字列中の埋め込みリテラルが追加された際に型検査を通す
-            HirExpression::Literal(_) => (),
-            HirExpression::Operation(operation) => match &mut **operation {
-                HirOperation::Substitute(left, right) => {
-                    self.expression(left);
-                    self.expression(right);
-                },
-                HirOperation::Add(left, right) => {
-                    self.expression(left);
-                    self.expression(right);
-                },
-                HirOperation::Subtract(left, right) => {
-                    self.expression(left);
-                    self.expression(right);
-                },
-                HirOperation::Multiply(left, right) => {
-                    self.expression(left);
-                    self.expression(right);
-                },
-                HirOperation::Not(term) => self.expression(term),
-                HirOperation::BitNot(term) => self.expression(term),
-                HirOperation::Negative(term) => self.expression(term),
-                HirOperation::Nonnize(term) => self.expression(term),
-                HirOperation::Propagate(term) => self.expression(term),
-                HirOperation::FunctionCall(term, arguments) => {
-                    self.expression(term);
-
-                    for each_argument in arguments {
-                        self.expression(each_argument);
-                    }
-                },
-                HirOperation::MemberAccess(left, right) => {
-                    self.expression(left);
-                    self.expression(right);
-                },
+            HirExpressionKind::Literal(_) | HirExpressionKind::Error => (),
+            HirExpressionKind::Operation(operation) => match &mut **operation {
                 HirOperation::Path(path) => self.path(path),
-                HirOperation::Group(term) => self.expression(term),
+                // Right term names a member of the left term, which is not in the scope.
+                HirOperation::MemberAccess(left, _) => self.expression(left),
+                other => for each_term in other.terms_mut() {
+                    self.expression(each_term);
+                },
+            },
+            HirExpressionKind::DataType(_) => unimplemented!(),
+            HirExpressionKind::Identifier(identifier) => {
+                // Identifiers of items are replaced with resolved paths.
+                if let Some(path_index) = self.identifier(identifier) {
+                    expr.kind = HirExpressionKind::Operation(Box::new(HirOperation::Path(HirPath::Resolved(path_index))));
+                }
+            },
+        }
+    }
+
+    // Returns the item which the identifier refers to. Arguments are left as identifiers.
+    pub(crate) fn identifier(&mut self, identifier: &HirIdentifier) -> Option<HirPathIndex> {
+        if self.arguments.iter().any(|v| v.id() == identifier.id()) {
+            return None;
+        }
+
+        let found = self.find_in_scope(identifier);
+
+        if found.is_none() {
+            self.errors.push(DataTypeError::UnknownIdentifier { span: identifier.span().clone() });
+        }
+
+        found
+    }
+
+    // Returns None when the data type cannot be determined without identifier resolution.
+    pub fn infer(expr: &HirExpression) -> Option<HirDataType> {
+        DataTypeChecker::infer_in(expr, &[])
+    }
+
+    // Identifiers are looked up in the formal arguments of the enclosing function.
+    pub fn infer_in(expr: &HirExpression, arguments: &[HirIdentifierBinding<HirFormalArgument>]) -> Option<HirDataType> {
+        match &expr.kind {
+            HirExpressionKind::Literal(literal) => match literal {
+                HirLiteral::Boolean(_) => Some(HirDataType::Primitive(HirPrimitiveDataType::Boolean)),
+                HirLiteral::Integer(integer) => Some(HirDataType::Primitive(integer.data_type.clone().unwrap_or(HirPrimitiveDataType::S32))),
+                HirLiteral::Float(float) => Some(HirDataType::Primitive(float.data_type.clone().unwrap_or(HirPrimitiveDataType::F64))),
+                HirLiteral::String(_) => Some(HirDataType::Primitive(HirPrimitiveDataType::String)),
+                HirLiteral::SelfValue => Some(HirDataType::Primitive(HirPrimitiveDataType::SelfType)),
+                HirLiteral::None => Some(HirDataType::Primitive(HirPrimitiveDataType::None)),
             },
-            HirExpression::DataType(_) => unimplemented!(),
-            HirExpression::Identifier(_) => unimplemented!(),
+            HirExpressionKind::Operation(operation) => match &**operation {
+                // Integer division truncates the quotient, so that arithmetic operations keep the data type of the left term.
+                HirOperation::Add(left, _)
+                    | HirOperation::Subtract(left, _)
+                    | HirOperation::Multiply(left, _)
+                    | HirOperation::Divide(left, _)
+                    | HirOperation::Remainder(left, _)
+                    | HirOperation::BitAnd(left, _)
+                    | HirOperation::BitOr(left, _)
+                    | HirOperation::BitXor(left, _)
+                    | HirOperation::LeftShift(left, _)
+                    | HirOperation::RightShift(left, _) => DataTypeChecker::infer_in(left, arguments),
+                HirOperation::Equal(_, _)
+                    | HirOperation::NotEqual(_, _)
+                    | HirOperation::LessThan(_, _)
+                    | HirOperation::LessThanOrEqual(_, _)
+                    | HirOperation::GreaterThan(_, _)
+                    | HirOperation::GreaterThanOrEqual(_, _)
+                    | HirOperation::And(_, _)
+                    | HirOperation::Or(_, _)
+                    | HirOperation::Not(_) => Some(HirDataType::Primitive(HirPrimitiveDataType::Boolean)),
+                HirOperation::Substitute(_, _)
+                    | HirOperation::AddSubstitute(_, _)
+                    | HirOperation::SubtractSubstitute(_, _)
+                    | HirOperation::MultiplySubstitute(_, _)
+                    | HirOperation::DivideSubstitute(_, _)
+                    | HirOperation::RemainderSubstitute(_, _) => Some(HirDataType::Primitive(HirPrimitiveDataType::None)),
+                HirOperation::BitNot(term) | HirOperation::Negative(term) | HirOperation::Group(term) => DataTypeChecker::infer_in(term, arguments),
+                // todo: infer types of path, function call and member access after identifier resolution
+                _ => None,
+            },
+            HirExpressionKind::Identifier(identifier) => arguments.iter()
+                .find(|v| v.identifier() == identifier)
+                .map(|v| v.value().data_type.clone()),
+            HirExpressionKind::DataType(_) | HirExpressionKind::Error => None,
         }
     }
 
@@ -91,17 +194,109 @@ impl<'a> DataTypeChecker<'a> {
         let path_index = match path {
             HirPath::Resolved(_) => unreachable!("path is already resolved"),
             HirPath::Unresolved(segments) => {
-                match self.path_tree.find(segments) {
-                    Some((path_index, _)) => path_index,
+                let span = segments.iter()
+                    .map(|v| v.span().clone())
+                    .reduce(|joined, each_span| joined.join(&each_span))
+                    .unwrap_or_default();
+
+                match self.find(segments) {
+                    Some(path_index) => {
+                        if !self.is_accessible(&path_index, span) {
+                            return;
+                        }
+
+                        path_index
+                    },
                     None => {
-                        self.errors.push(DataTypeError::UnknownIdentifier);
+                        let error = match self.undeclared_hako(segments) {
+                            Some(hako) => DataTypeError::UndeclaredDependency { hako, span },
+                            None => DataTypeError::UnknownIdentifier { span },
+                        };
+
+                        self.errors.push(error);
                         return;
                     },
                 }
             },
         };
 
-        let new_path = HirPath::Resolved(path_index.clone());
+        let new_path = HirPath::Resolved(path_index);
         let _ = std::mem::replace(path, new_path);
     }
+
+    // Reports the item when it is not accessible from the current module. Items are not restricted if no module.
+    fn is_accessible(&mut self, path_index: &HirPathIndex, span: Span) -> bool {
+        let current_module = match self.current_module {
+            Some(v) => v,
+            None => return true,
+        };
+
+        if self.path_tree.is_accessible(&current_module, path_index) {
+            return true;
+        }
+
+        if let (Some(path_node), Some(name)) = (self.path_tree.get(path_index), self.path_tree.path_string(path_index)) {
+            self.errors.push(DataTypeError::PrivateItemAccess { name, declaration: path_node.span.clone(), span });
+        }
+
+        false
+    }
+
+    // Name of the hako which the path starts with when the current hako does not depend on it.
+    // Local modules and imports shadow hako names.
+    fn undeclared_hako(&self, segments: &[HirPathSegment]) -> Option<String> {
+        let current_hako = self.current_hako?;
+        let first_segment = segments.first()?;
+
+        if HirPathTree::is_relative(segments) {
+            return None;
+        }
+
+        if let Some(current_module) = &self.current_module {
+            if self.path_tree.find_in_scope(current_module, first_segment).is_some() {
+                return None;
+            }
+        }
+
+        let (hako_index, _) = self.path_tree.find_hako(first_segment)?;
+
+        if self.path_tree.is_visible_hako(&current_hako, hako_index) {
+            return None;
+        }
+
+        Some(first_segment.id().to_string())
+    }
+
+    // First segment is looked up in the current module and its imports, then in hakos.
+    fn find(&mut self, segments: &[HirPathSegment]) -> Option<HirPathIndex> {
+        let first_segment = segments.first()?;
+
+        if HirPathTree::is_relative(segments) {
+            return self.path_tree.find_relative(&self.current_module?, segments);
+        }
+
+        if let Some(path_index) = self.find_in_scope(first_segment) {
+            return self.path_tree.find_descendant(&path_index, &segments[1..]);
+        }
+
+        let found = match &self.current_hako {
+            Some(current_hako) => self.path_tree.find_from(current_hako, segments),
+            None => self.path_tree.find(segments),
+        };
+
+        found.map(|(path_index, _)| *path_index)
+    }
+
+    // Same as HirPathTree::find_in_scope() but records the use declaration which the name is imported by.
+    fn find_in_scope(&mut self, segment: &HirPathSegment) -> Option<HirPathIndex> {
+        let module = self.current_module?;
+
+        if let Some((path_index, _)) = self.path_tree.find_child(&self.path_tree.get(&module)?.children, segment) {
+            return Some(*path_index);
+        }
+
+        let (position, path_index) = self.path_tree.find_import(&module, segment)?;
+        self.used_imports.insert((module, position));
+        Some(path_index)
+    }
 }
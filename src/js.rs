@@ -2,56 +2,114 @@ pub mod code;
 pub mod ir;
 pub mod jsify;
 
-use volt::parser::ParserError;
-use crate::hir::hirify::{TreeHirifier, AstHako, AstModule};
-use crate::hir::type_check::DataTypeChecker;
+use std::path::PathBuf;
+use volt::{parser::ParserError, tree::SyntaxTree};
+use crate::diagnostic::{Diagnostic, json::DiagnosticJsonWriter};
+use crate::hako::loader::{HakoLoaderError, LoadedHako, LoadedModule, SOURCE_EXTENSION};
+use crate::hako::manifest::DEFAULT_ENTRY;
+use crate::span::{FileId, SourceMap};
+use crate::hir::Hir;
+use crate::hir::hirify::{TreeHirifier, TreeHirifierError, TreeHirifierLog, AstHako};
+use crate::hir::resolve::{UseResolver, UseResolverError};
+use crate::hir::type_check::{DataTypeChecker, DataTypeError, DataTypeWarning};
 use crate::{Compiler, ParserResult, Syntax, RuleId};
 use crate::js::jsify::JsGenerator;
 use crate::js::code::JsCodeGenerator;
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct JsTranspilerOptions;
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsTranspilerOptions {
+    // Throws a RangeError on integer division by zero instead of producing Infinity or NaN.
+    pub checked_arithmetic: bool,
+}
 
+// Each variant corresponds to the phase which reported the error.
 #[derive(Clone, Debug, PartialEq)]
 pub enum JsTranspilerError {
     ParserError(ParserError),
+    HirifierError(TreeHirifierError),
+    ResolverError(UseResolverError),
+    DataTypeError(DataTypeError),
+    LoaderError(HakoLoaderError),
+}
+
+impl From<JsTranspilerError> for Diagnostic {
+    fn from(value: JsTranspilerError) -> Self {
+        match value {
+            JsTranspilerError::ParserError(e) => e.into(),
+            JsTranspilerError::HirifierError(e) => e.into(),
+            JsTranspilerError::ResolverError(e) => e.into(),
+            JsTranspilerError::DataTypeError(e) => e.into(),
+            JsTranspilerError::LoaderError(e) => e.into(),
+        }
+    }
+}
+
+// Warnings do not prevent code generation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsTranspilerWarning {
+    DataTypeWarning(DataTypeWarning),
+}
+
+impl From<JsTranspilerWarning> for Diagnostic {
+    fn from(value: JsTranspilerWarning) -> Self {
+        match value {
+            JsTranspilerWarning::DataTypeWarning(e) => e.into(),
+        }
+    }
+}
+
+// Code is generated only when no error is reported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsTranspilerOutput {
+    pub code: Option<String>,
+    pub errors: Vec<JsTranspilerError>,
+    pub warnings: Vec<JsTranspilerWarning>,
+    // Files which spans of the errors and warnings refer to.
+    pub source_map: SourceMap,
+}
+
+impl JsTranspilerOutput {
+    pub fn is_success(&self) -> bool {
+        self.errors.len() == 0
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self.errors.iter().map(|v| v.clone().into()).collect();
+        diagnostics.extend(self.warnings.iter().map(|v| v.clone().into()));
+        Diagnostic::mark_conflicting_suggestions(&mut diagnostics);
+        diagnostics
+    }
+
+    pub fn diagnostics_json(&self) -> String {
+        DiagnosticJsonWriter::new(&self.source_map).write(&self.diagnostics())
+    }
 }
 
 pub struct JsTranspiler {
     options: JsTranspilerOptions,
 }
 
-impl Compiler<&str, Result<String, JsTranspilerError>, JsTranspilerOptions> for JsTranspiler {
+impl Compiler<&str, JsTranspilerOutput, JsTranspilerOptions> for JsTranspiler {
     fn new(options: JsTranspilerOptions) -> Self {
         Self { options }
     }
 
-    // todo: support multiple module files.
-    fn compile(&self, input: &str) -> Result<String, JsTranspilerError> {
+    // Input is registered as the entry file like `main.kr`.
+    fn compile(&self, input: &str) -> JsTranspilerOutput {
+        let mut source_map = SourceMap::new();
+        let file_id = source_map.add(format!("{}.{}", DEFAULT_ENTRY, SOURCE_EXTENSION), input.to_string());
+
         let tree = match self.parse(input) {
             Ok(v) => v,
-            Err(e) => return Err(JsTranspilerError::ParserError(e)),
-        };
-
-        // todo: handle errors
-        let (mut hir, tree_hirifier_errors) = TreeHirifier::hirify(vec![
-            &AstHako {
-                id: "test".to_string(),
-                modules: vec![
-                    AstModule {
-                        id: "main".to_string(),
-                        node: &tree.root,
-                        submodules: Vec::new(),
-                    },
-                ]
+            Err(e) => return JsTranspilerOutput {
+                code: None,
+                errors: vec![JsTranspilerError::ParserError(e)],
+                warnings: Vec::new(),
+                source_map,
             },
-        ]);
+        };
 
-        // todo: handle errors
-        let data_type_errors = DataTypeChecker::check(&hir.path_tree, &mut hir.items);
-        let js = JsGenerator::generate(&hir);
-        let js_code = JsCodeGenerator::generate(&js);
-        Ok(js_code)
+        self.compile_hako(&JsTranspiler::source_hako(tree, file_id), Vec::new(), source_map)
     }
 
     fn parse(&self, input: &str) -> ParserResult {
@@ -59,3 +117,60 @@ impl Compiler<&str, Result<String, JsTranspilerError>, JsTranspilerOptions> for
         volt.parse(input, &RuleId("Main::main".to_string()))
     }
 }
+
+impl JsTranspiler {
+    // Source without a file is compiled as a single-file hako whose only module is the entry like `main::main`.
+    pub fn source_hako(tree: SyntaxTree, file_id: FileId) -> LoadedHako {
+        LoadedHako {
+            id: DEFAULT_ENTRY.to_string(),
+            path: PathBuf::new(),
+            dependencies: Vec::new(),
+            modules: vec![
+                LoadedModule {
+                    id: DEFAULT_ENTRY.to_string(),
+                    path: None,
+                    file_id,
+                    tree,
+                    submodules: Vec::new(),
+                },
+            ],
+        }
+    }
+
+    pub fn analyze_hakos(&self, hakos: Vec<&AstHako>) -> (Hir, Vec<JsTranspilerError>, Vec<JsTranspilerWarning>) {
+        let (mut hir, tree_hirifier_logs) = TreeHirifier::hirify(hakos);
+
+        let mut errors: Vec<JsTranspilerError> = tree_hirifier_logs.into_iter().map(|v| match v {
+            TreeHirifierLog::Error(e) => JsTranspilerError::HirifierError(e),
+        }).collect();
+
+        // Use declarations are resolved before type checking to look up imported names.
+        let resolver_errors = UseResolver::resolve(&mut hir.path_tree);
+        errors.extend(resolver_errors.into_iter().map(|v| JsTranspilerError::ResolverError(v)));
+
+        // Type checking continues after hirifier errors to report as many errors as possible.
+        let (data_type_errors, data_type_warnings) = DataTypeChecker::check(&hir.path_tree, &mut hir.items);
+        errors.extend(data_type_errors.into_iter().map(|v| JsTranspilerError::DataTypeError(v)));
+        let warnings = data_type_warnings.into_iter().map(|v| JsTranspilerWarning::DataTypeWarning(v)).collect();
+
+        (hir, errors, warnings)
+    }
+
+    // Modules which failed to be loaded are excluded from the analysis. Broken HIR must not reach code generation.
+    pub fn compile_hako(&self, hako: &LoadedHako, loader_errors: Vec<HakoLoaderError>, source_map: SourceMap) -> JsTranspilerOutput {
+        let (hir, analyzer_errors, warnings) = self.analyze_hakos(vec![&hako.ast()]);
+        let mut errors: Vec<JsTranspilerError> = loader_errors.into_iter().map(|v| JsTranspilerError::LoaderError(v)).collect();
+        errors.extend(analyzer_errors);
+
+        if errors.len() != 0 {
+            return JsTranspilerOutput { code: None, errors, warnings, source_map };
+        }
+
+        JsTranspilerOutput { code: Some(self.generate(&hir)), errors, warnings, source_map }
+    }
+
+    pub fn generate(&self, hir: &Hir) -> String {
+        let js = JsGenerator::generate(hir, &self.options);
+        JsCodeGenerator::generate(&js)
+    }
+}
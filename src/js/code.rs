@@ -10,6 +10,16 @@ impl JsCodeGenerator {
     pub fn item(item: &JsItem) -> String {
         match item {
             JsItem::Function(function) => JsCodeGenerator::function(function),
+            JsItem::RuntimeHelper(helper) => JsCodeGenerator::runtime_helper(helper),
+        }
+    }
+
+    pub fn runtime_helper(helper: &JsRuntimeHelper) -> String {
+        match helper {
+            JsRuntimeHelper::CheckDivisor => format!(
+                "function {id}(v){{if(v==0)throw new RangeError(\"division by zero\");return v}}",
+                id = helper.id(),
+            ),
         }
     }
 
@@ -40,23 +50,19 @@ impl JsCodeGenerator {
         match literal {
             JsLiteral::Boolean(boolean) => boolean.to_string(),
             JsLiteral::Integer(integer) => integer.to_string(),
+            JsLiteral::BigInt(integer) => format!("{}n", integer),
         }
     }
 
     pub fn operation(operation: &JsOperation) -> String {
         match operation {
-            JsOperation::Add(left, right) => format!("{}+{}", JsCodeGenerator::expression(left), JsCodeGenerator::expression(right)),
-            JsOperation::Subtract(left, right) => format!("{}-{}", JsCodeGenerator::expression(left), JsCodeGenerator::expression(right)),
-            JsOperation::Multiply(left, right) => format!("{}*{}", JsCodeGenerator::expression(left), JsCodeGenerator::expression(right)),
-            JsOperation::Not(term) => format!("!{}", JsCodeGenerator::expression(term)),
-            JsOperation::BitNot(term) => format!("~{}", JsCodeGenerator::expression(term)),
-            JsOperation::Negative(term) => format!("-{}", JsCodeGenerator::expression(term)),
+            JsOperation::Prefix(symbol, term) => format!("{}{}", symbol, JsCodeGenerator::expression(term)),
+            JsOperation::Infix(symbol, left, right) => format!("{}{}{}", JsCodeGenerator::expression(left), symbol, JsCodeGenerator::expression(right)),
             JsOperation::FunctionCall(term, arguments) => format!(
                 "{}({})",
                 JsCodeGenerator::expression(term),
                 arguments.iter().map(|v| JsCodeGenerator::expression(v)).collect::<Vec<String>>().join(","),
             ),
-            JsOperation::MemberAccess(left, right) => format!("{}.{}", JsCodeGenerator::expression(left), JsCodeGenerator::expression(right)),
             JsOperation::Group(term) => format!("({})", JsCodeGenerator::expression(term)),
         }
     }
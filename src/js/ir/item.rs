@@ -3,6 +3,7 @@ use super::stmt::JsStatement;
 #[derive(Clone, Debug, PartialEq)]
 pub enum JsItem {
     Function(JsFunction),
+    RuntimeHelper(JsRuntimeHelper),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -11,3 +12,17 @@ pub struct JsFunction {
     pub arguments: Vec<String>,
     pub statements: Vec<JsStatement>,
 }
+
+// Helper functions which are emitted only when generated code calls them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsRuntimeHelper {
+    CheckDivisor,
+}
+
+impl JsRuntimeHelper {
+    pub fn id(&self) -> &'static str {
+        match self {
+            JsRuntimeHelper::CheckDivisor => "k_check_divisor",
+        }
+    }
+}
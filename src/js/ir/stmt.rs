@@ -25,17 +25,14 @@ pub enum JsLiteral {
     Boolean(bool),
     // todo: add JsIntegerLiteral
     Integer(String),
+    BigInt(String),
 }
 
+// Operator symbols are given by the operator table.
 #[derive(Clone, Debug, PartialEq)]
 pub enum JsOperation {
-    Add(JsExpression, JsExpression),
-    Subtract(JsExpression, JsExpression),
-    Multiply(JsExpression, JsExpression),
-    Not(JsExpression),
-    BitNot(JsExpression),
-    Negative(JsExpression),
+    Prefix(&'static str, JsExpression),
+    Infix(&'static str, JsExpression, JsExpression),
     FunctionCall(JsExpression, Vec<JsExpression>),
-    MemberAccess(JsExpression, JsExpression),
     Group(JsExpression),
 }
@@ -1,27 +1,57 @@
+use crate::ast::operator::table::{OperatorAssociativity, OperatorDefinition, OperatorFixity};
 use crate::hir::*;
 use crate::hir::ir::{expr::*, item::*, path::*};
+use crate::hir::type_check::DataTypeChecker;
+use crate::js::JsTranspilerOptions;
 use crate::js::ir::{*, item::*, stmt::*};
 
+// Representation of Karin number in JS.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsNumberKind {
+    // Integers up to 32 bits are represented by Number and truncated after division.
+    Integer,
+    // 64-bit integers are represented by BigInt, whose division truncates and throws on zero by itself.
+    BigInt,
+    Float,
+    // Data type is not determined, so that JS arithmetic is used as it is.
+    Unknown,
+}
+
 pub struct JsGenerator<'a> {
     path_tree: &'a HirPathTree,
+    // Formal arguments of the function being generated, which give data types of identifiers.
+    arguments: Vec<HirIdentifierBinding<HirFormalArgument>>,
+    pub(crate) checked_arithmetic: bool,
+    pub(crate) runtime_helpers: Vec<JsRuntimeHelper>,
 }
 
 impl<'a> JsGenerator<'a> {
     pub(crate) fn new(path_tree: &'a HirPathTree) -> JsGenerator<'a> {
-        JsGenerator { path_tree }
+        JsGenerator {
+            path_tree,
+            arguments: Vec::new(),
+            checked_arithmetic: false,
+            runtime_helpers: Vec::new(),
+        }
     }
 
-    pub fn generate(hir: &'a Hir) -> Js {
+    pub fn generate(hir: &'a Hir, options: &JsTranspilerOptions) -> Js {
         let mut generator = JsGenerator::new(&hir.path_tree);
-        let items = hir.items.iter().map(|v| generator.item(v)).collect();
+        generator.checked_arithmetic = options.checked_arithmetic;
+
+        let mut generated_items: Vec<JsItem> = hir.items.iter().map(|v| generator.item(v)).collect();
+        let mut items: Vec<JsItem> = generator.runtime_helpers.iter().map(|v| JsItem::RuntimeHelper(v.clone())).collect();
+        items.append(&mut generated_items);
         Js { items }
     }
 
     pub fn item(&mut self, item: &HirPathIndexBinding<HirItem>) -> JsItem {
         let path_index = item.index();
 
-        match item.value() {
-            HirItem::Function(function) => {
+        match &item.value().kind {
+            HirItemKind::Function(function) => {
+                self.arguments = function.arguments.clone();
+
                 JsItem::Function(
                     JsFunction {
                         id: format!("i_{}", path_index),
@@ -35,9 +65,11 @@ impl<'a> JsGenerator<'a> {
     }
 
     pub fn statement(&mut self, expr: &HirExpression) -> JsStatement {
-        match expr {
-            HirExpression::Literal(literal) => JsStatement::Expression(JsExpression::Literal(self.literal(literal))),
-            HirExpression::Operation(operation) => JsStatement::Expression(self.operation(operation)),
+        match &expr.kind {
+            HirExpressionKind::Literal(literal) => JsStatement::Expression(JsExpression::Literal(self.literal(literal))),
+            HirExpressionKind::Operation(operation) => JsStatement::Expression(self.operation(operation)),
+            // Identifiers of items are resolved into paths, so that remaining ones are arguments.
+            HirExpressionKind::Identifier(identifier) => JsStatement::Expression(JsExpression::Identifier(identifier.id().to_string())),
             _ => unimplemented!(),
         }
     }
@@ -47,7 +79,10 @@ impl<'a> JsGenerator<'a> {
             HirLiteral::Boolean(boolean) => JsLiteral::Boolean(*boolean),
             HirLiteral::Integer(integer) => {
                 // todo: support exponent and add test case
-                JsLiteral::Integer(integer.value.clone())
+                match integer.data_type {
+                    Some(HirPrimitiveDataType::S64) | Some(HirPrimitiveDataType::U64) => JsLiteral::BigInt(integer.value.clone()),
+                    _ => JsLiteral::Integer(integer.value.clone()),
+                }
             },
             _ => unimplemented!(),
         }
@@ -56,27 +91,233 @@ impl<'a> JsGenerator<'a> {
     pub fn operation(&mut self, operation: &HirOperation) -> JsExpression {
         // todo: convert statement to expression
         let js_operation = match operation {
-            HirOperation::Substitute(_, _) => unimplemented!(),
-            HirOperation::Add(left, right) => JsOperation::Add(self.statement(left).into(), self.statement(right).into()),
-            HirOperation::Subtract(left, right) => JsOperation::Subtract(self.statement(left).into(), self.statement(right).into()),
-            HirOperation::Multiply(left, right) => JsOperation::Multiply(self.statement(left).into(), self.statement(right).into()),
-            HirOperation::Not(term) => JsOperation::Not(self.statement(term).into()),
-            HirOperation::BitNot(term) => JsOperation::BitNot(self.statement(term).into()),
-            HirOperation::Negative(term) => JsOperation::Negative(self.statement(term).into()),
+            HirOperation::Divide(left, right) => return self.division(left, right),
+            // Compound substitution cannot truncate the quotient, so that it is expanded like `a = Math.trunc(a / b)`.
+            // Left term is evaluated twice, so that only terms without side effects are expanded.
+            // todo: bind member access target to a temporary variable once its data type is inferred
+            HirOperation::DivideSubstitute(left, right) if JsGenerator::is_reevaluable(left) && self.operation_number_kind(left, right) == JsNumberKind::Integer => {
+                let js_left = self.statement(left).into();
+                let js_right = self.division(left, right);
+                JsGenerator::infix(&HirOperator::Substitute, js_left, js_right)
+            },
+            HirOperation::Remainder(left, right) | HirOperation::RemainderSubstitute(left, right) => {
+                let js_left = self.number_term(left, right);
+                let js_right = self.divisor(left, right);
+                JsGenerator::infix(&operation.operator().unwrap(), js_left, js_right)
+            },
+            HirOperation::And(left, right) | HirOperation::Or(left, right) => {
+                let js_left = self.logical_term(left);
+                let js_right = self.logical_term(right);
+                JsGenerator::infix(&operation.operator().unwrap(), js_left, js_right)
+            },
             HirOperation::Nonnize(_) => unimplemented!(),
             HirOperation::Propagate(_) => unimplemented!(),
-            HirOperation::FunctionCall(term, arguments) => JsOperation::FunctionCall(
-                self.statement(term).into(),
-                arguments.iter().map(|v| self.statement(v).into()).collect(),
-            ),
-            HirOperation::MemberAccess(left, right) => JsOperation::MemberAccess(self.statement(left).into(), self.statement(right).into()),
+            HirOperation::FunctionCall(term, arguments) => {
+                let js_term = self.statement(term).into();
+                let js_arguments = arguments.iter().map(|v| self.statement(v).into()).collect();
+                JsGenerator::function_call(js_term, js_arguments)
+            },
             HirOperation::Path(path) => return JsExpression::Identifier(self.path(path)),
             HirOperation::Group(term) => JsOperation::Group(self.statement(term).into()),
+            _ => {
+                let operator = operation.operator().expect("operation must have an operator");
+
+                match (OperatorDefinition::find(&operator).map(|v| v.fixity), operation.terms().as_slice()) {
+                    (Some(OperatorFixity::Prefix), [term]) => JsGenerator::prefix(&operator, self.statement(term).into()),
+                    (Some(OperatorFixity::Infix), [left, right]) => {
+                        let js_left = self.number_term(left, right);
+                        let js_right = self.number_term(right, left);
+                        JsGenerator::infix(&operator, js_left, js_right)
+                    },
+                    _ => unreachable!("unexpected fixity or number of terms"),
+                }
+            },
         };
 
         JsExpression::Operation(Box::new(js_operation))
     }
 
+    // JS precedence differs from Karin's like `&` binds looser than `==`, so that terms which JS binds looser than the operator are grouped.
+    pub(crate) fn prefix(operator: &HirOperator, term: JsExpression) -> JsOperation {
+        let symbol = JsGenerator::js_symbol(operator);
+        let precedence = JsGenerator::js_precedence(operator);
+
+        // `- -a` must not be joined into decrement.
+        let is_nested_negative = matches!(&term, JsExpression::Operation(v) if matches!(&**v, JsOperation::Prefix("-", _)));
+        let term = JsGenerator::group_if(term, |v| v < precedence || (is_nested_negative && symbol == "-"));
+
+        JsOperation::Prefix(symbol, term)
+    }
+
+    // Terms of the same precedence are grouped on the side which the operator does not associate toward.
+    pub(crate) fn infix(operator: &HirOperator, left: JsExpression, right: JsExpression) -> JsOperation {
+        let symbol = JsGenerator::js_symbol(operator);
+        let precedence = JsGenerator::js_precedence(operator);
+
+        let (groups_equal_left, groups_equal_right) = match OperatorDefinition::find(operator).map(|v| v.associativity) {
+            Some(OperatorAssociativity::Left) => (false, true),
+            Some(OperatorAssociativity::Right) => (true, false),
+            _ => (true, true),
+        };
+
+        let left = JsGenerator::group_if(left, |v| v < precedence || (v == precedence && groups_equal_left));
+        let right = JsGenerator::group_if(right, |v| v < precedence || (v == precedence && groups_equal_right));
+
+        JsOperation::Infix(symbol, left, right)
+    }
+
+    pub(crate) fn function_call(term: JsExpression, arguments: Vec<JsExpression>) -> JsOperation {
+        let precedence = JsGenerator::js_precedence(&HirOperator::FunctionCall(Vec::new()));
+        JsOperation::FunctionCall(JsGenerator::group_if(term, |v| v < precedence), arguments)
+    }
+
+    fn group_if<F: Fn(usize) -> bool>(term: JsExpression, predicate: F) -> JsExpression {
+        match JsGenerator::expression_precedence(&term) {
+            Some(precedence) if predicate(precedence) => JsExpression::Operation(Box::new(JsOperation::Group(term))),
+            _ => term,
+        }
+    }
+
+    // None when other operators cannot split the expression like literals and groups.
+    pub(crate) fn expression_precedence(expr: &JsExpression) -> Option<usize> {
+        let operation = match expr {
+            JsExpression::Operation(operation) => operation,
+            _ => return None,
+        };
+
+        match &**operation {
+            JsOperation::Prefix(symbol, _) => OperatorDefinition::find_by_js_symbol(symbol, OperatorFixity::Prefix).and_then(|v| v.js_precedence),
+            JsOperation::Infix(symbol, _, _) => OperatorDefinition::find_by_js_symbol(symbol, OperatorFixity::Infix).and_then(|v| v.js_precedence),
+            JsOperation::FunctionCall(_, _) => Some(JsGenerator::js_precedence(&HirOperator::FunctionCall(Vec::new()))),
+            JsOperation::Group(_) => None,
+        }
+    }
+
+    pub(crate) fn js_symbol(operator: &HirOperator) -> &'static str {
+        match OperatorDefinition::find(operator).and_then(|v| v.js_symbol) {
+            Some(v) => v,
+            None => unimplemented!("operator has no symbol in JS"),
+        }
+    }
+
+    pub(crate) fn js_precedence(operator: &HirOperator) -> usize {
+        match OperatorDefinition::find(operator).and_then(|v| v.js_precedence) {
+            Some(v) => v,
+            None => unimplemented!("operator has no precedence in JS"),
+        }
+    }
+
+    pub(crate) fn number_kind(&self, term: &HirExpression) -> JsNumberKind {
+        match DataTypeChecker::infer_in(term, &self.arguments) {
+            Some(HirDataType::Primitive(data_type)) => match data_type {
+                HirPrimitiveDataType::S8
+                    | HirPrimitiveDataType::S16
+                    | HirPrimitiveDataType::S32
+                    | HirPrimitiveDataType::Ssize
+                    | HirPrimitiveDataType::U8
+                    | HirPrimitiveDataType::U16
+                    | HirPrimitiveDataType::U32
+                    | HirPrimitiveDataType::Usize => JsNumberKind::Integer,
+                HirPrimitiveDataType::S64 | HirPrimitiveDataType::U64 => JsNumberKind::BigInt,
+                HirPrimitiveDataType::F32 | HirPrimitiveDataType::F64 => JsNumberKind::Float,
+                _ => JsNumberKind::Unknown,
+            },
+            _ => JsNumberKind::Unknown,
+        }
+    }
+
+    // Integer term is converted into BigInt when the other term is BigInt, so that the operation is done in BigInt.
+    pub(crate) fn operation_number_kind(&self, left: &HirExpression, right: &HirExpression) -> JsNumberKind {
+        match (self.number_kind(left), self.number_kind(right)) {
+            (JsNumberKind::Integer, JsNumberKind::BigInt) => JsNumberKind::BigInt,
+            (left_kind, _) => left_kind,
+        }
+    }
+
+    // JS throws TypeError on mixing BigInt and Number, so that integer term is converted like `1` into `1n`.
+    pub fn number_term(&mut self, term: &HirExpression, other: &HirExpression) -> JsExpression {
+        let js_term = self.statement(term).into();
+
+        if self.number_kind(term) != JsNumberKind::Integer || self.number_kind(other) != JsNumberKind::BigInt {
+            return js_term;
+        }
+
+        match js_term {
+            JsExpression::Literal(JsLiteral::Integer(value)) => JsExpression::Literal(JsLiteral::BigInt(value)),
+            _ => JsExpression::Operation(Box::new(
+                JsGenerator::function_call(JsExpression::Identifier("BigInt".to_string()), vec![js_term]),
+            )),
+        }
+    }
+
+    // JS division always produces a float, so that integer quotient is truncated toward zero.
+    pub fn division(&mut self, left: &HirExpression, right: &HirExpression) -> JsExpression {
+        let js_left = self.number_term(left, right);
+        let js_right = self.divisor(left, right);
+        let quotient = JsExpression::Operation(Box::new(JsGenerator::infix(&HirOperator::Divide, js_left, js_right)));
+
+        match self.operation_number_kind(left, right) {
+            JsNumberKind::Integer => JsExpression::Operation(Box::new(
+                JsGenerator::function_call(
+                    JsExpression::Operation(Box::new(
+                        JsGenerator::infix(
+                            &HirOperator::MemberAccess,
+                            JsExpression::Identifier("Math".to_string()),
+                            JsExpression::Identifier("trunc".to_string()),
+                        ),
+                    )),
+                    vec![quotient],
+                ),
+            )),
+            JsNumberKind::BigInt | JsNumberKind::Float | JsNumberKind::Unknown => quotient,
+        }
+    }
+
+    // Karin's remainder takes the sign of the dividend as JS `%` does, so that only the divisor needs to be checked.
+    pub fn divisor(&mut self, left: &HirExpression, right: &HirExpression) -> JsExpression {
+        let js_right = self.number_term(right, left);
+
+        if !self.checked_arithmetic || self.operation_number_kind(left, right) != JsNumberKind::Integer {
+            return js_right;
+        }
+
+        let helper = JsRuntimeHelper::CheckDivisor;
+        let helper_id = helper.id().to_string();
+
+        if !self.runtime_helpers.contains(&helper) {
+            self.runtime_helpers.push(helper);
+        }
+
+        JsExpression::Operation(Box::new(
+            JsGenerator::function_call(JsExpression::Identifier(helper_id), vec![js_right]),
+        ))
+    }
+
+    // Evaluating identifiers and paths again yields the same value without side effects.
+    pub(crate) fn is_reevaluable(term: &HirExpression) -> bool {
+        match &term.kind {
+            HirExpressionKind::Identifier(_) => true,
+            HirExpressionKind::Operation(operation) => match &**operation {
+                HirOperation::Path(_) => true,
+                HirOperation::Group(term) => JsGenerator::is_reevaluable(term),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    // JS logical operators return one of their terms, so that non-boolean terms are converted into boolean with `!!`.
+    pub fn logical_term(&mut self, term: &HirExpression) -> JsExpression {
+        let js_term: JsExpression = self.statement(term).into();
+
+        if DataTypeChecker::infer_in(term, &self.arguments) == Some(HirDataType::Primitive(HirPrimitiveDataType::Boolean)) {
+            return js_term;
+        }
+
+        let not = |term: JsExpression| JsExpression::Operation(Box::new(JsGenerator::prefix(&HirOperator::Not, term)));
+        not(not(js_term))
+    }
+
     pub fn path(&mut self, path: &HirPath) -> String {
         match path {
             HirPath::Resolved(index) => format!("i_{index}"),
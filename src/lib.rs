@@ -1,6 +1,10 @@
 pub mod ast;
+pub mod cli;
+pub mod diagnostic;
+pub mod hako;
 pub mod hir;
 pub mod js;
+pub mod span;
 #[cfg(test)]
 mod tests;
 
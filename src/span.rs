@@ -0,0 +1,133 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FileId(usize);
+
+impl fmt::Display for FileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<usize> for FileId {
+    fn from(value: usize) -> Self {
+        FileId(value)
+    }
+}
+
+impl From<FileId> for usize {
+    fn from(value: FileId) -> Self {
+        value.0
+    }
+}
+
+// Line and column are zero-based like volt positions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SourcePosition {
+    pub index: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourcePosition {
+    pub fn new(index: usize, line: usize, column: usize) -> SourcePosition {
+        SourcePosition { index, line, column }
+    }
+
+    // Converts a byte index of the content like the ones of TOML errors.
+    pub fn from_byte_index(content: &str, byte_index: usize) -> SourcePosition {
+        let mut position = SourcePosition::default();
+
+        for (each_index, each_char) in content.char_indices() {
+            if each_index >= byte_index {
+                break;
+            }
+
+            position.advance(each_char);
+        }
+
+        position
+    }
+
+    // Moves to the end of the text which starts at this position.
+    pub fn advance_by(&self, text: &str) -> SourcePosition {
+        let mut position = self.clone();
+        text.chars().for_each(|v| position.advance(v));
+        position
+    }
+
+    fn advance(&mut self, next_char: char) {
+        self.index += 1;
+
+        if next_char == '\n' {
+            self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Span {
+    pub file_id: FileId,
+    pub start: SourcePosition,
+    // Exclusive.
+    pub end: SourcePosition,
+}
+
+impl Span {
+    pub fn new(file_id: FileId, start: SourcePosition, end: SourcePosition) -> Span {
+        Span { file_id, start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.index.saturating_sub(self.start.index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn join(&self, other: &Span) -> Span {
+        let start = if self.start.index <= other.start.index { &self.start } else { &other.start };
+        let end = if self.end.index >= other.end.index { &self.end } else { &other.end };
+        Span::new(self.file_id, start.clone(), end.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceFile {
+    pub id: FileId,
+    pub name: String,
+    pub content: String,
+}
+
+impl SourceFile {
+    // Line is zero-based.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        self.content.lines().nth(line)
+    }
+}
+
+// File ids are given in order of addition.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    pub fn add(&mut self, name: String, content: String) -> FileId {
+        let id = FileId(self.files.len());
+        self.files.push(SourceFile { id, name, content });
+        id
+    }
+
+    pub fn get(&self, id: FileId) -> Option<&SourceFile> {
+        self.files.get(id.0)
+    }
+}
@@ -1,3 +1,35 @@
 mod ast;
+mod cli;
+mod diagnostic;
+mod hako;
 mod hir;
 mod js;
+mod span;
+
+use std::{env, fs, ops::Deref, path::{Path, PathBuf}, process};
+
+// Directory of a test which is removed on drop. Each test uses its own directory since tests run in parallel.
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    pub(crate) fn new(name: &str) -> TempDir {
+        let path = env::temp_dir().join(format!("karin-test-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
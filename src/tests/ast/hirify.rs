@@ -2,8 +2,11 @@ use std::collections::BTreeMap;
 use crate::hir::*;
 use crate::hir::hirify::*;
 use crate::hir::ir::{expr::*, item::*, path::*};
+use crate::ast::operator::OperationParserError;
+use crate::ast::syntax::Syntax;
+use crate::span::{FileId, SourcePosition, Span};
 use speculate::speculate;
-use volt::{*, tree::*};
+use volt::{*, rule::RuleId, tree::*};
 
 speculate!{
     before {
@@ -14,17 +17,24 @@ speculate!{
         let get_operator = |operator: HirOperator|
             HirOperationToken::Operator(operator);
 
+        // Leaves of hand-built trees start at the beginning of the file.
+        #[allow(unused)]
+        let leaf_span = |len: usize| Span::new(FileId::default(), SourcePosition::default(), SourcePosition::new(len, 0, len));
+
         #[allow(unused)]
         let get_integer_expression = |value: usize|
-            HirExpression::Literal(
-                HirLiteral::Integer(
-                    HirIntegerLiteral {
-                        data_type: None,
-                        base: HirIntegerBase::Decimal,
-                        value: value.to_string(),
-                        exponent: None,
-                    },
+            HirExpression::new(
+                HirExpressionKind::Literal(
+                    HirLiteral::Integer(
+                        HirIntegerLiteral {
+                            data_type: None,
+                            base: HirIntegerBase::Decimal,
+                            value: value.to_string(),
+                            exponent: None,
+                        },
+                    ),
                 ),
+                leaf_span(value.to_string().len()),
             );
 
         #[allow(unused)]
@@ -36,6 +46,7 @@ speculate!{
             let (hir, _) = TreeHirifier::hirify(vec![
                 &AstHako {
                     id: "h".to_string(),
+                    dependencies: Vec::new(),
                     modules: Vec::new(),
                 },
             ]);
@@ -46,6 +57,7 @@ speculate!{
                     hako_indexes: vec![
                         HirPathIndex::from(0),
                     ],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
@@ -53,6 +65,7 @@ speculate!{
                             kind: HirPathKind::Hako,
                             parent: None,
                             children: Vec::new(),
+                            span: Span::default(),
                         },
                     )]),
                 },
@@ -63,9 +76,11 @@ speculate!{
             let (hir, _) = TreeHirifier::hirify(vec![
                 &AstHako {
                     id: "h".to_string(),
+                    dependencies: Vec::new(),
                     modules: vec![
                         AstModule {
                             id: "m".to_string(),
+                            file_id: FileId::default(),
                             node: &empty_tree().root,
                             submodules: Vec::new(),
                         },
@@ -79,6 +94,7 @@ speculate!{
                     hako_indexes: vec![
                         HirPathIndex::from(0),
                     ],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([
                         (
                             HirPathIndex::from(0),
@@ -87,6 +103,7 @@ speculate!{
                                 kind: HirPathKind::Hako,
                                 parent: None,
                                 children: vec![1.into()],
+                                span: Span::default(),
                             },
                         ),
                         (
@@ -96,6 +113,7 @@ speculate!{
                                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                                 parent: Some(0.into()),
                                 children: Vec::new(),
+                                span: Span::default(),
                             },
                         ),
                     ]),
@@ -118,9 +136,11 @@ speculate!{
             let (hir, _) = TreeHirifier::hirify(vec![
                 &AstHako {
                     id: "h".to_string(),
+                    dependencies: Vec::new(),
                     modules: vec![
                         AstModule {
                             id: "m".to_string(),
+                            file_id: FileId::default(),
                             node: syntax_child.into_node(),
                             submodules: Vec::new(),
                         },
@@ -134,6 +154,7 @@ speculate!{
                     hako_indexes: vec![
                         HirPathIndex::from(0),
                     ],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([
                         (
                             HirPathIndex::from(0),
@@ -142,6 +163,7 @@ speculate!{
                                 kind: HirPathKind::Hako,
                                 parent: None,
                                 children: vec![1.into()],
+                                span: Span::default(),
                             },
                         ),
                         (
@@ -151,15 +173,17 @@ speculate!{
                                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                                 parent: Some(0.into()),
                                 children: vec![2.into()],
+                                span: leaf_span(1),
                             },
                         ),
                         (
                             HirPathIndex::from(2),
                             HirPathNode {
                                 id: "f".into(),
-                                kind: HirPathKind::Function,
+                                kind: HirPathKind::Function { accessibility: HirAccessibility::Private },
                                 parent: Some(1.into()),
                                 children: Vec::new(),
+                                span: leaf_span(1),
                             },
                         ),
                     ]),
@@ -169,19 +193,108 @@ speculate!{
             assert_eq!(hir.items, vec![
                 HirPathIndexBinding::new(
                     2.into(),
-                    HirItem::Function(
-                        HirFunction {
-                            accessibility: HirAccessibility::Private,
-                            return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
-                            arguments: Vec::new(),
-                            expressions: Vec::new(),
-                        }
+                    HirItem::new(
+                        HirItemKind::Function(
+                            HirFunction {
+                                accessibility: HirAccessibility::Private,
+                                return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
+                                arguments: Vec::new(),
+                                expressions: Vec::new(),
+                            }
+                        ),
+                        leaf_span(1),
                     ),
                 ),
             ]);
         }
     }
 
+    describe "span" {
+        it "locates items, expressions and paths in their files" {
+            let mut volt = Syntax::generate_volt(1024);
+            let main_tree = volt.parse("fn f() {1 + 2}", &RuleId("Main::main".to_string())).unwrap();
+            let sub_tree = volt.parse("fn g() {}", &RuleId("Main::main".to_string())).unwrap();
+            let position = |index: usize| SourcePosition::new(index, 0, index);
+
+            let (hir, logs) = TreeHirifier::hirify(vec![
+                &AstHako {
+                    id: "h".to_string(),
+                    dependencies: Vec::new(),
+                    modules: vec![
+                        AstModule {
+                            id: "main".to_string(),
+                            file_id: 1.into(),
+                            node: &main_tree.root,
+                            submodules: vec![
+                                AstModule {
+                                    id: "sub".to_string(),
+                                    file_id: 2.into(),
+                                    node: &sub_tree.root,
+                                    submodules: Vec::new(),
+                                },
+                            ],
+                        },
+                    ],
+                },
+            ]);
+
+            assert_eq!(logs, Vec::new());
+
+            let node_span = |segments: Vec<&str>| {
+                let segments: Vec<HirPathSegment> = segments.into_iter().map(|v| v.into()).collect();
+                hir.path_tree.find(&segments).unwrap().1.span.clone()
+            };
+
+            // Hako is located at the start of its first module.
+            assert_eq!(node_span(vec!["h"]).file_id, 1.into());
+            assert_eq!(node_span(vec!["h", "main"]).file_id, 1.into());
+            assert!(node_span(vec!["h", "main"]).start == position(0));
+            assert_eq!(node_span(vec!["h", "main", "sub"]).file_id, 2.into());
+            assert_eq!(node_span(vec!["h", "main", "f"]).file_id, 1.into());
+            assert!(node_span(vec!["h", "main", "f"]).start == position(0));
+            assert_eq!(node_span(vec!["h", "main", "sub", "g"]).file_id, 2.into());
+
+            let f_index = *hir.path_tree.find(&vec!["h".into(), "main".into(), "f".into()]).unwrap().0;
+            let f_item = hir.items.iter().find(|v| *v.index() == f_index).unwrap().value();
+            assert_eq!(f_item.span, node_span(vec!["h", "main", "f"]));
+
+            let expression = match &f_item.kind {
+                HirItemKind::Function(function) => &function.expressions[0],
+                _ => unreachable!(),
+            };
+
+            // Operation is located from the first term to the last one.
+            assert_eq!(expression.span, Span::new(1.into(), position(8), position(13)));
+
+            let terms = match &expression.kind {
+                HirExpressionKind::Operation(operation) => operation.terms(),
+                _ => unreachable!(),
+            };
+
+            assert_eq!(terms[0].span, Span::new(1.into(), position(8), position(9)));
+            assert_eq!(terms[1].span, Span::new(1.into(), position(12), position(13)));
+        }
+
+        it "locates recovery leaf across lines" {
+            let tree = Syntax::generate_volt(1024).parse("fn\nx\nfn main() {}", &RuleId("Main::main".to_string())).unwrap();
+            let mut analyzer = new_analyzer();
+            analyzer.log_syntax_errors(&tree.root);
+
+            assert_eq!(
+                analyzer.logs,
+                vec![
+                    TreeHirifierLog::Error(
+                        TreeHirifierError::InvalidSyntax {
+                            name: "invalid_item".to_string(),
+                            source: "fn\nx".to_string(),
+                            span: Span::new(FileId::default(), SourcePosition::new(0, 0, 0), SourcePosition::new(4, 1, 1)),
+                        },
+                    ),
+                ],
+            );
+        }
+    }
+
     describe "hako" {
         it "generates a hako path" {
             let mut analyzer = new_analyzer();
@@ -189,6 +302,7 @@ speculate!{
             analyzer.hako(
                 &AstHako {
                     id: "h".to_string(),
+                    dependencies: Vec::new(),
                     modules: Vec::new(),
                 },
             );
@@ -197,6 +311,7 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![HirPathIndex::from(0)],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
@@ -204,6 +319,7 @@ speculate!{
                             kind: HirPathKind::Hako,
                             parent: None,
                             children: Vec::new(),
+                            span: Span::default(),
                         },
                     )]),
                 },
@@ -216,6 +332,7 @@ speculate!{
             analyzer.hako(
                 &AstHako {
                     id: "h".to_string(),
+                    dependencies: Vec::new(),
                     modules: Vec::new(),
                 },
             );
@@ -224,6 +341,7 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![HirPathIndex::from(0)],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
@@ -231,6 +349,7 @@ speculate!{
                             kind: HirPathKind::Hako,
                             parent: None,
                             children: Vec::new(),
+                            span: Span::default(),
                         },
                     )]),
                 },
@@ -245,6 +364,7 @@ speculate!{
             let path_index = analyzer.module(
                 &AstModule {
                     id: "m".to_string(),
+                    file_id: FileId::default(),
                     node: &empty_tree().root,
                     submodules: Vec::new(),
                 },
@@ -257,6 +377,7 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
@@ -264,6 +385,7 @@ speculate!{
                             kind: HirPathKind::Module { use_declarations: Vec::new() },
                             parent: Some(HirPathIndex::from(100)),
                             children: Vec::new(),
+                            span: Span::default(),
                         },
                     )]),
                 },
@@ -276,10 +398,12 @@ speculate!{
             analyzer.module(
                 &AstModule {
                     id: "m".to_string(),
+                    file_id: FileId::default(),
                     node: &empty_tree().root,
                     submodules: vec![
                         AstModule {
                             id: "sm".to_string(),
+                            file_id: FileId::default(),
                             node: &empty_tree().root,
                             submodules: Vec::new(),
                         },
@@ -292,6 +416,7 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([
                         (
                             HirPathIndex::from(0),
@@ -300,6 +425,7 @@ speculate!{
                                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                                 parent: Some(HirPathIndex::from(100)),
                                 children: vec![1.into()],
+                                span: Span::default(),
                             },
                         ),
                         (
@@ -309,6 +435,7 @@ speculate!{
                                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                                 parent: Some(HirPathIndex::from(0)),
                                 children: Vec::new(),
+                                span: Span::default(),
                             },
                         ),
                     ]),
@@ -316,6 +443,101 @@ speculate!{
             );
         }
 
+        it "skips invalid items and logs syntax errors" {
+            let mut analyzer = new_analyzer();
+            let tree = tree!(
+                node!("Main::main" => [
+                    node!("Item::item" => [
+                        error!("invalid_item", [leaf!(";")]),
+                    ]),
+                ])
+            );
+
+            analyzer.module(
+                &AstModule {
+                    id: "m".to_string(),
+                    file_id: FileId::default(),
+                    node: &tree.root,
+                    submodules: Vec::new(),
+                },
+                HirPathIndex::from(100),
+            );
+
+            assert_eq!(analyzer.items, Vec::new());
+            assert_eq!(analyzer.path_tree.get(&HirPathIndex::from(0)).unwrap().children, Vec::new());
+
+            assert_eq!(
+                analyzer.logs,
+                vec![TreeHirifierLog::Error(TreeHirifierError::InvalidSyntax { name: "invalid_item".to_string(), source: ";".to_string(), span: leaf_span(1) })],
+            );
+        }
+
+        it "hirifies invalid expression into error expression" {
+            let mut analyzer = new_analyzer();
+
+            assert_eq!(
+                analyzer.expression(
+                    node!("Expression::expression" => [
+                        node!("Literal::literal" => [
+                            node!("Literal::number" => [
+                                node!("value" => [
+                                    node!("Literal::decimal_number" => [
+                                        error!("starts_with_zero", [leaf!("01")]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ]).into_node(),
+                ),
+                HirExpression::new(HirExpressionKind::Error, leaf_span(2)),
+            );
+
+            assert_eq!(
+                analyzer.logs,
+                vec![TreeHirifierLog::Error(TreeHirifierError::InvalidSyntax { name: "starts_with_zero".to_string(), source: "01".to_string(), span: leaf_span(2) })],
+            );
+        }
+
+        it "reports syntax error in nested expression once" {
+            let mut analyzer = new_analyzer();
+
+            assert_eq!(
+                analyzer.expression(
+                    node!("Expression::expression" => [
+                        node!("Operation::operation" => [
+                            node!("Expression::pure_expression" => [
+                                node!("Literal::literal" => [
+                                    node!("Literal::number" => [
+                                        node!("value" => [
+                                            node!("Literal::decimal_number" => [leaf!("0")]),
+                                        ]),
+                                    ]),
+                                ]),
+                            ]),
+                            node!("operator" => [leaf!("+")]),
+                            node!("Expression::pure_expression" => [
+                                node!("Literal::literal" => [
+                                    node!("Literal::number" => [
+                                        node!("value" => [
+                                            node!("Literal::decimal_number" => [
+                                                error!("starts_with_zero", [leaf!("01")]),
+                                            ]),
+                                        ]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ]).into_node(),
+                ),
+                HirExpression::new(HirExpressionKind::Error, leaf_span(2)),
+            );
+
+            assert_eq!(
+                analyzer.logs,
+                vec![TreeHirifierLog::Error(TreeHirifierError::InvalidSyntax { name: "starts_with_zero".to_string(), source: "01".to_string(), span: leaf_span(2) })],
+            );
+        }
+
         it "generates subitem paths and their structure" {
             let syntax_child = node!("Main::main" => [
                 node!("Item::item" => [
@@ -335,6 +557,7 @@ speculate!{
             analyzer.module(
                 &AstModule {
                     id: "m".to_string(),
+                    file_id: FileId::default(),
                     node: syntax_child.into_node(),
                     submodules: Vec::new(),
                 },
@@ -345,6 +568,7 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([
                         (
                             HirPathIndex::from(0),
@@ -353,15 +577,17 @@ speculate!{
                                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                                 parent: Some(HirPathIndex::from(100)),
                                 children: vec![1.into()],
+                                span: leaf_span(1),
                             },
                         ),
                         (
                             HirPathIndex::from(1),
                             HirPathNode {
                                 id: "f".into(),
-                                kind: HirPathKind::Function,
+                                kind: HirPathKind::Function { accessibility: HirAccessibility::Private },
                                 parent: Some(HirPathIndex::from(0)),
                                 children: Vec::new(),
+                                span: leaf_span(1),
                             },
                         ),
                     ]),
@@ -373,25 +599,124 @@ speculate!{
                 vec![
                     HirPathIndexBinding::new(
                         1.into(),
-                        HirItem::Function(
-                            HirFunction {
-                                accessibility: HirAccessibility::Private,
-                                return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
-                                arguments: Vec::new(),
-                                expressions: Vec::new(),
-                            },
+                        HirItem::new(
+                            HirItemKind::Function(
+                                HirFunction {
+                                    accessibility: HirAccessibility::Private,
+                                    return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
+                                    arguments: Vec::new(),
+                                    expressions: Vec::new(),
+                                },
+                            ),
+                            leaf_span(1),
                         ),
                     ),
                 ],
             );
         }
+
+        it "reports duplicate definitions and keeps the first one" {
+            let mut analyzer = new_analyzer();
+            let syntax_child = node!("Main::main" => [
+                node!("Item::item" => [
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ]),
+                node!("Item::item" => [
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ]),
+            ]);
+
+            analyzer.module(
+                &AstModule {
+                    id: "m".to_string(),
+                    file_id: FileId::default(),
+                    node: syntax_child.into_node(),
+                    submodules: vec![
+                        AstModule {
+                            id: "f".to_string(),
+                            file_id: FileId::default(),
+                            node: &empty_tree().root,
+                            submodules: Vec::new(),
+                        },
+                    ],
+                },
+                HirPathIndex::from(100),
+            );
+
+            assert_eq!(analyzer.path_tree.get(&0.into()).unwrap().children, vec![1.into()]);
+            // Duplicates are removed from the path tree and items.
+            assert_eq!(analyzer.path_tree.get(&2.into()), None);
+            assert_eq!(analyzer.path_tree.get(&3.into()), None);
+            assert_eq!(analyzer.items, Vec::new());
+
+            assert_eq!(
+                analyzer.logs,
+                vec![
+                    TreeHirifierLog::Error(TreeHirifierError::DuplicateDefinition { name: "f".to_string(), previous: Span::default(), span: leaf_span(1) }),
+                    TreeHirifierLog::Error(TreeHirifierError::DuplicateDefinition { name: "f".to_string(), previous: Span::default(), span: leaf_span(1) }),
+                ],
+            );
+        }
+
+        it "reports import conflicting with item" {
+            let mut analyzer = new_analyzer();
+            let syntax_child = node!("Main::main" => [
+                node!("Item::item" => [
+                    node!("UseDeclaration::use_declaration" => [leaf!("a"), leaf!("f")]),
+                ]),
+                node!("Item::item" => [
+                    node!("UseDeclaration::use_declaration" => [leaf!("a"), node!("UseDeclaration::glob" => [])]),
+                ]),
+                node!("Item::item" => [
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ]),
+            ]);
+
+            analyzer.module(
+                &AstModule {
+                    id: "m".to_string(),
+                    file_id: FileId::default(),
+                    node: syntax_child.into_node(),
+                    submodules: Vec::new(),
+                },
+                HirPathIndex::from(100),
+            );
+
+            assert_eq!(
+                analyzer.logs,
+                vec![TreeHirifierLog::Error(TreeHirifierError::ImportConflict { name: "f".to_string(), declaration: leaf_span(1), span: leaf_span(1) })],
+            );
+        }
     }
 
     describe "identifier" {
         it "generates identifier" {
             let syntax_child = node!("Identifier::identifier" => [leaf!("id")]);
             let mut analyzer = new_analyzer();
-            assert_eq!(analyzer.identifier(syntax_child.into_node()), "id".to_string());
+            assert_eq!(analyzer.identifier(syntax_child.into_node()), "id".into());
+        }
+
+        it "locates identifier by its leaf" {
+            let syntax_child = node!("Identifier::identifier" => [leaf!("id")]);
+            let mut analyzer = new_analyzer();
+            let identifier = analyzer.identifier(syntax_child.into_node());
+            let expected_span = Span::new(FileId::default(), SourcePosition::new(0, 0, 0), SourcePosition::new(2, 0, 2));
+            assert_eq!(identifier.span(), &expected_span);
         }
     }
 
@@ -446,13 +771,15 @@ speculate!{
                 analyzer.path_tree,
                 HirPathTree {
                     hako_indexes: vec![],
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
                             id: "f".into(),
-                            kind: HirPathKind::Function,
+                            kind: HirPathKind::Function { accessibility: HirAccessibility::Private },
                             parent: Some(HirPathIndex::from(100)),
                             children: Vec::new(),
+                            span: leaf_span(1),
                         },
                     )]),
                 },
@@ -467,7 +794,9 @@ speculate!{
                     ]).into_node(),
                     100.into(),
                 ),
-                ItemHirifierResult::UseDeclaration(HirPath::Unresolved(vec!["a".into()])),
+                ItemHirifierResult::UseDeclaration(vec![
+                    HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into()]), HirUseKind::Named("a".into()), leaf_span(1)),
+                ]),
             );
         }
 
@@ -487,6 +816,29 @@ speculate!{
                 ItemHirifierResult::ItemPathIndex(0.into()),
             );
         }
+
+        it "carries doc comments into item" {
+            let mut analyzer = new_analyzer();
+
+            analyzer.item(
+                node!("Item::item" => [
+                    node!("Item::documentation" => [
+                        leaf!("/// a"),
+                        leaf!("///"),
+                        leaf!("///  b"),
+                    ]),
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ]).into_node(),
+                100.into(),
+            );
+
+            assert_eq!(analyzer.items[0].value().documentation, Some("a\n\n b".to_string()));
+        }
     }
 
     describe "use declaration" {
@@ -495,7 +847,7 @@ speculate!{
                 new_analyzer().use_declaration(
                     node!("UseDeclaration::use_declaration" => [leaf!("a")]).into_node(),
                 ),
-                HirPath::Unresolved(vec!["a".into()]),
+                vec![HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into()]), HirUseKind::Named("a".into()), leaf_span(1))],
             );
         }
 
@@ -504,7 +856,7 @@ speculate!{
                 new_analyzer().use_declaration(
                     node!("UseDeclaration::use_declaration" => [leaf!("self")]).into_node(),
                 ),
-                HirPath::Unresolved(vec!["self".into()]),
+                vec![HirUseDeclaration::new(HirPath::Unresolved(vec!["self".into()]), HirUseKind::Named("self".into()), leaf_span(4))],
             );
         }
 
@@ -517,7 +869,7 @@ speculate!{
 
             assert_eq!(
                 result,
-                HirPath::Unresolved(vec!["self".into()]),
+                vec![HirUseDeclaration::new(HirPath::Unresolved(vec!["self".into()]), HirUseKind::Named("self".into()), leaf_span(4))],
             );
 
             assert_eq!(analyzer.logs, Vec::new());
@@ -530,12 +882,52 @@ speculate!{
 
             assert_eq!(
                 result,
-                HirPath::Unresolved(vec!["a".into(), "self".into()]),
+                vec![HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into(), "self".into()]), HirUseKind::Named("self".into()), leaf_span(4))],
+            );
+
+            assert_eq!(
+                analyzer.logs,
+                vec![TreeHirifierLog::Error(TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment: "self".to_string(), span: leaf_span(4) })],
             );
+        }
+
+        it "allows super after self and other super only" {
+            let mut analyzer = new_analyzer();
+            analyzer.use_declaration(node!("UseDeclaration::use_declaration" => [leaf!("self"), leaf!("super"), leaf!("super"), leaf!("a")]).into_node());
+            assert_eq!(analyzer.logs, Vec::new());
+
+            let mut analyzer = new_analyzer();
+            analyzer.use_declaration(node!("UseDeclaration::use_declaration" => [leaf!("a"), leaf!("super")]).into_node());
 
             assert_eq!(
                 analyzer.logs,
-                vec![TreeHirifierLog::Error(TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment: "self".to_string() })],
+                vec![TreeHirifierLog::Error(TreeHirifierError::PathSegmentMustLocateFirstPosition { path_segment: "super".to_string(), span: leaf_span(5) })],
+            );
+        }
+
+        it "flattens grouped, glob and aliased trees" {
+            assert_eq!(
+                new_analyzer().use_declaration(
+                    node!("UseDeclaration::use_declaration" => [
+                        leaf!("a"),
+                        node!("UseDeclaration::group" => [
+                            node!("UseDeclaration::use_tree" => [leaf!("b")]),
+                            node!("UseDeclaration::use_tree" => [
+                                leaf!("c"),
+                                node!("UseDeclaration::glob" => []),
+                            ]),
+                            node!("UseDeclaration::use_tree" => [
+                                leaf!("d"),
+                                node!("UseDeclaration::alias" => [leaf!("e")]),
+                            ]),
+                        ]),
+                    ]).into_node(),
+                ),
+                vec![
+                    HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into(), "b".into()]), HirUseKind::Named("b".into()), leaf_span(1)),
+                    HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into(), "c".into()]), HirUseKind::Glob, leaf_span(1)),
+                    HirUseDeclaration::new(HirPath::Unresolved(vec!["a".into(), "d".into()]), HirUseKind::Named("e".into()), leaf_span(1)),
+                ],
             );
         }
     }
@@ -569,7 +961,7 @@ speculate!{
                     ]).into_node(),
                 ),
                 (
-                    "f".to_string(),
+                    "f".into(),
                     HirFunction {
                         accessibility: HirAccessibility::Private,
                         return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
@@ -583,7 +975,7 @@ speculate!{
                             ),
                         ],
                         expressions: vec![
-                            HirExpression::Literal(HirLiteral::Boolean(true)),
+                            HirExpression::new(HirExpressionKind::Literal(HirLiteral::Boolean(true)), leaf_span(4)),
                         ],
                     },
                 ),
@@ -601,7 +993,7 @@ speculate!{
                     ]).into_node(),
                 ),
                 (
-                    "f".to_string(),
+                    "f".into(),
                     HirFunction {
                         accessibility: HirAccessibility::Private,
                         return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
@@ -624,7 +1016,7 @@ speculate!{
                     ]).into_node(),
                 ),
                 (
-                    "f".to_string(),
+                    "f".into(),
                     HirFunction {
                         accessibility: HirAccessibility::Private,
                         return_type: HirDataType::Primitive(HirPrimitiveDataType::Usize),
@@ -733,7 +1125,7 @@ speculate!{
 
                 assert_eq!(
                     analyzer.logs,
-                    vec![TreeHirifierLog::Error(TreeHirifierError::SelfArgumentMustLocateFirstPosition)],
+                    vec![TreeHirifierLog::Error(TreeHirifierError::SelfArgumentMustLocateFirstPosition { span: leaf_span(4) })],
                 );
             }
         }
@@ -771,13 +1163,16 @@ speculate!{
                         ]),
                     ]).into_node(),
                 ),
-                HirExpression::Operation(
-                    Box::new(
-                        HirOperation::Add(
-                            get_integer_expression(0),
-                            get_integer_expression(1),
+                HirExpression::new(
+                    HirExpressionKind::Operation(
+                        Box::new(
+                            HirOperation::Add(
+                                get_integer_expression(0),
+                                get_integer_expression(1),
+                            ),
                         ),
                     ),
+                    leaf_span(1),
                 ),
             );
         }
@@ -791,7 +1186,7 @@ speculate!{
                         ]),
                     ]).into_node(),
                 ),
-                HirExpression::Literal(HirLiteral::Boolean(true)),
+                HirExpression::new(HirExpressionKind::Literal(HirLiteral::Boolean(true)), leaf_span(4)),
             );
         }
 
@@ -802,7 +1197,7 @@ speculate!{
                         node!("Identifier::identifier" => [leaf!("id")]),
                     ]).into_node(),
                 ),
-                HirExpression::Identifier("id".into()),
+                HirExpression::new(HirExpressionKind::Identifier("id".into()), leaf_span(2)),
             );
         }
 
@@ -817,7 +1212,7 @@ speculate!{
                         ]),
                     ]).into_node(),
                 ),
-                HirExpression::DataType(HirDataType::Primitive(HirPrimitiveDataType::Usize)),
+                HirExpression::new(HirExpressionKind::DataType(HirDataType::Primitive(HirPrimitiveDataType::Usize)), leaf_span(5)),
             );
         }
     }
@@ -848,17 +1243,60 @@ speculate!{
                         ]),
                     ]).into_node(),
                 ),
-                HirExpression::Operation(
-                    Box::new(
-                        HirOperation::Add(
-                            get_integer_expression(0),
-                            get_integer_expression(1),
+                HirExpression::new(
+                    HirExpressionKind::Operation(
+                        Box::new(
+                            HirOperation::Add(
+                                get_integer_expression(0),
+                                get_integer_expression(1),
+                            ),
                         ),
                     ),
+                    leaf_span(1),
                 ),
             );
         }
 
+        it "records invalid operation and returns placeholder" {
+            let integer_node = |value: &str| node!("Expression::pure_expression" => [
+                node!("Literal::literal" => [
+                    node!("Literal::number" => [
+                        node!("value" => [
+                            node!("Literal::decimal_number" => [leaf!(value)]),
+                        ]),
+                    ]),
+                ]),
+            ]);
+
+            let mut analyzer = new_analyzer();
+
+            assert_eq!(
+                // 0 < 1 < 2
+                analyzer.operation(
+                    node!("Operation::operation" => [
+                        integer_node("0"),
+                        node!("operator" => [leaf!("<")]),
+                        integer_node("1"),
+                        node!("operator" => [leaf!("<")]),
+                        integer_node("2"),
+                    ]).into_node(),
+                ),
+                HirExpression::new(HirExpressionKind::Error, leaf_span(1)),
+            );
+
+            assert_eq!(
+                analyzer.logs,
+                vec![
+                    TreeHirifierLog::Error(
+                        TreeHirifierError::InvalidOperation {
+                            error: OperationParserError::NonAssociativeChain { token_index: 3 },
+                            span: leaf_span(1),
+                        },
+                    ),
+                ],
+            );
+        }
+
         describe "operator" {
             // todo: add operators
 
@@ -881,13 +1319,16 @@ speculate!{
                                 ]),
                             ]).into_node(),
                         ),
-                        HirExpression::Operation(
-                            Box::new(
-                                HirOperation::FunctionCall(
-                                    get_integer_expression(0),
-                                    Vec::new(),
+                        HirExpression::new(
+                            HirExpressionKind::Operation(
+                                Box::new(
+                                    HirOperation::FunctionCall(
+                                        get_integer_expression(0),
+                                        Vec::new(),
+                                    ),
                                 ),
                             ),
+                            leaf_span(1),
                         ),
                     );
 
@@ -933,16 +1374,19 @@ speculate!{
                                 ]),
                             ]).into_node(),
                         ),
-                        HirExpression::Operation(
-                            Box::new(
-                                HirOperation::FunctionCall(
-                                    get_integer_expression(0),
-                                    vec![
-                                        get_integer_expression(1),
-                                        get_integer_expression(2),
-                                    ],
+                        HirExpression::new(
+                            HirExpressionKind::Operation(
+                                Box::new(
+                                    HirOperation::FunctionCall(
+                                        get_integer_expression(0),
+                                        vec![
+                                            get_integer_expression(1),
+                                            get_integer_expression(2),
+                                        ],
+                                    ),
                                 ),
                             ),
+                            leaf_span(1),
                         ),
                     );
                 }
@@ -1,6 +1,6 @@
 use speculate::speculate;
-use crate::hir::ir::expr::*;
-use crate::ast::operator::OperationParser;
+use crate::hir::ir::{expr::*, path::HirPath};
+use crate::ast::operator::{OperationParser, OperationParserError, table::*};
 
 speculate!{
     before {
@@ -11,20 +11,20 @@ speculate!{
         #[allow(unused)]
         let get_string_term = |s: &str|
             HirOperationToken::Term(
-                HirExpression::Literal(
+                HirExpression::from(HirExpressionKind::Literal(
                     HirLiteral::String(s.to_string()),
-                ),
+                )),
             );
 
         #[allow(unused)]
         let get_operation_expression = |operation: HirOperation|
-            HirExpression::Operation(Box::new(operation));
+            HirExpression::from(HirExpressionKind::Operation(Box::new(operation)));
 
         #[allow(unused)]
         let get_string_expression = |s: &str|
-            HirExpression::Literal(
+            HirExpression::from(HirExpressionKind::Literal(
                 HirLiteral::String(s.to_string()),
-            );
+            ));
     }
 
     it "parses into operation expression" {
@@ -36,14 +36,14 @@ speculate!{
                     get_string_term("b"),
                 ],
             ),
-            Ok(HirExpression::Operation(
+            Ok(HirExpression::from(HirExpressionKind::Operation(
                 Box::new(
                     HirOperation::Add(
                         get_string_expression("a"),
                         get_string_expression("b"),
                     ),
                 ),
-            )),
+            ))),
         );
     }
 
@@ -156,6 +156,163 @@ speculate!{
                 );
             }
 
+            it "chains right-associative operators from right" {
+                assert_eq!(
+                    // left: a = b = c
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::Substitute),
+                            get_string_term("b"),
+                            get_operator(HirOperator::Substitute),
+                            get_string_term("c"),
+                        ],
+                    ),
+                    // right: a b c = =
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_string_term("b"),
+                        get_string_term("c"),
+                        get_operator(HirOperator::Substitute),
+                        get_operator(HirOperator::Substitute),
+                    ]),
+                );
+            }
+
+            it "rejects chain of non-associative operators" {
+                assert_eq!(
+                    // left: a < b < c
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::LessThan),
+                            get_string_term("b"),
+                            get_operator(HirOperator::LessThan),
+                            get_string_term("c"),
+                        ],
+                    ),
+                    Err(OperationParserError::NonAssociativeChain { token_index: 3 }),
+                );
+
+                assert_eq!(
+                    // left: a == b != c
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::Equal),
+                            get_string_term("b"),
+                            get_operator(HirOperator::NotEqual),
+                            get_string_term("c"),
+                        ],
+                    ),
+                    Err(OperationParserError::NonAssociativeChain { token_index: 3 }),
+                );
+            }
+
+            it "allows non-associative operators separated by lower precedence operator" {
+                assert_eq!(
+                    // left: a < b && b < c
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::LessThan),
+                            get_string_term("b"),
+                            get_operator(HirOperator::And),
+                            get_string_term("b"),
+                            get_operator(HirOperator::LessThan),
+                            get_string_term("c"),
+                        ],
+                    ),
+                    // right: a b < b c < &&
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_string_term("b"),
+                        get_operator(HirOperator::LessThan),
+                        get_string_term("b"),
+                        get_string_term("c"),
+                        get_operator(HirOperator::LessThan),
+                        get_operator(HirOperator::And),
+                    ]),
+                );
+            }
+
+            it "precedes comparison to logical operator" {
+                assert_eq!(
+                    // left: a || b && c == d
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::Or),
+                            get_string_term("b"),
+                            get_operator(HirOperator::And),
+                            get_string_term("c"),
+                            get_operator(HirOperator::Equal),
+                            get_string_term("d"),
+                        ],
+                    ),
+                    // right: a b c d == && ||
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_string_term("b"),
+                        get_string_term("c"),
+                        get_string_term("d"),
+                        get_operator(HirOperator::Equal),
+                        get_operator(HirOperator::And),
+                        get_operator(HirOperator::Or),
+                    ]),
+                );
+            }
+
+            it "precedes arithmetic operator to bitwise operator" {
+                assert_eq!(
+                    // left: a & b << c % d
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::BitAnd),
+                            get_string_term("b"),
+                            get_operator(HirOperator::LeftShift),
+                            get_string_term("c"),
+                            get_operator(HirOperator::Remainder),
+                            get_string_term("d"),
+                        ],
+                    ),
+                    // right: a b c d % << &
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_string_term("b"),
+                        get_string_term("c"),
+                        get_string_term("d"),
+                        get_operator(HirOperator::Remainder),
+                        get_operator(HirOperator::LeftShift),
+                        get_operator(HirOperator::BitAnd),
+                    ]),
+                );
+            }
+
+            it "reflects right-associativity of compound substitution" {
+                assert_eq!(
+                    // left: a += b / c
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_string_term("a"),
+                            get_operator(HirOperator::AddSubstitute),
+                            get_string_term("b"),
+                            get_operator(HirOperator::Divide),
+                            get_string_term("c"),
+                        ],
+                    ),
+                    // right: a b c / +=
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_string_term("b"),
+                        get_string_term("c"),
+                        get_operator(HirOperator::Divide),
+                        get_operator(HirOperator::AddSubstitute),
+                    ]),
+                );
+            }
+
             it "group" {
                 assert_eq!(
                     // left: a * (b + c)
@@ -250,6 +407,23 @@ speculate!{
                 );
             }
 
+            it "accepts bit not operator" {
+                assert_eq!(
+                    // left: ~a
+                    OperationParser::into_postfix_notation(
+                        vec![
+                            get_operator(HirOperator::BitNot),
+                            get_string_term("a"),
+                        ],
+                    ),
+                    // right: a ~
+                    Ok(vec![
+                        get_string_term("a"),
+                        get_operator(HirOperator::BitNot),
+                    ]),
+                );
+            }
+
             it "mixes with infix operator" {
                 assert_eq!(
                     // left: !a + !b
@@ -415,6 +589,19 @@ speculate!{
 
     describe "precedence index" {
         // todo: add test case to test group precedence index
+
+        it "binds multiplicative operator tighter than additive operator" {
+            assert!(
+                OperationParser::get_operator_precedence(&HirOperator::Multiply)
+                    > OperationParser::get_operator_precedence(&HirOperator::Add),
+            );
+        }
+
+        it "gives associativity to each operator" {
+            assert_eq!(OperationParser::get_operator_associativity(&HirOperator::Add), OperatorAssociativity::Left);
+            assert_eq!(OperationParser::get_operator_associativity(&HirOperator::Substitute), OperatorAssociativity::Right);
+            assert_eq!(OperationParser::get_operator_associativity(&HirOperator::LessThan), OperatorAssociativity::None);
+        }
     }
 
     describe "expression constructor" {
@@ -425,9 +612,9 @@ speculate!{
                         get_string_term("a"),
                         get_operator(HirOperator::Not),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Not(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Not(
                         get_string_expression("a"),
-                    )))),
+                    ))))),
                 );
             }
 
@@ -438,11 +625,11 @@ speculate!{
                         get_operator(HirOperator::Not),
                         get_operator(HirOperator::Negative),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Negative(
-                        HirExpression::Operation(Box::new(HirOperation::Not(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Negative(
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Not(
                             get_string_expression("a"),
-                        )))
-                    )))),
+                        ))))
+                    ))))),
                 );
             }
 
@@ -453,9 +640,9 @@ speculate!{
                         get_operator(HirOperator::GroupBegin),
                         get_operator(HirOperator::GroupEnd),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Group(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Group(
                         get_string_expression("a"),
-                    )))),
+                    ))))),
                 );
             }
         }
@@ -468,10 +655,10 @@ speculate!{
                         get_string_term("b"),
                         get_operator(HirOperator::Add),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Add(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(
                         get_string_expression("a"),
                         get_string_expression("b"),
-                    )))),
+                    ))))),
                 );
             }
 
@@ -484,13 +671,13 @@ speculate!{
                         get_string_term("c"),
                         get_operator(HirOperator::Add),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Add(
-                        HirExpression::Operation(Box::new(HirOperation::Add(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(
                             get_string_expression("a"),
                             get_string_expression("b"),
-                        ))),
+                        )))),
                         get_string_expression("c"),
-                    )))),
+                    ))))),
                 );
 
                 assert_eq!(
@@ -501,13 +688,13 @@ speculate!{
                         get_operator(HirOperator::Multiply),
                         get_operator(HirOperator::Add),
                     ]),
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Add(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(
                         get_string_expression("a"),
-                        HirExpression::Operation(Box::new(HirOperation::Multiply(
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Multiply(
                             get_string_expression("b"),
                             get_string_expression("c"),
-                        ))),
-                    )))),
+                        )))),
+                    ))))),
                 );
             }
         }
@@ -529,21 +716,126 @@ speculate!{
                         get_operator(HirOperator::Multiply),
                     ]),
                     // right: Multiply(Not(Propagate(Group(Add(a, b)))), c)
-                    Ok(HirExpression::Operation(Box::new(HirOperation::Multiply(
-                        HirExpression::Operation(Box::new(HirOperation::Not(
-                            HirExpression::Operation(Box::new(HirOperation::Propagate(
-                                HirExpression::Operation(Box::new(HirOperation::Group(
-                                    HirExpression::Operation(Box::new(HirOperation::Add(
+                    Ok(HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Multiply(
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Not(
+                            HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Propagate(
+                                HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Group(
+                                    HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(
                                         get_string_expression("a"),
                                         get_string_expression("b"),
-                                    ))),
-                                ))),
-                            ))),
-                        ))),
+                                    )))),
+                                )))),
+                            )))),
+                        )))),
                         get_string_expression("c"),
-                    )))),
+                    ))))),
                 );
             }
         }
     }
+    describe "error" {
+        it "reports index of token in input sequence" {
+            assert_eq!(
+                // left: a + + b
+                OperationParser::parse(vec![
+                    get_string_term("a"),
+                    get_operator(HirOperator::Add),
+                    get_operator(HirOperator::Add),
+                    get_string_term("b"),
+                ]),
+                Err(OperationParserError::InvalidLengthOfTerm { token_index: 1 }),
+            );
+
+            assert_eq!(
+                // left: a b
+                OperationParser::parse(vec![
+                    get_string_term("a"),
+                    get_string_term("b"),
+                ]),
+                Err(OperationParserError::InvalidLengthOfTerm { token_index: 0 }),
+            );
+        }
+
+        it "rejects unbalanced group" {
+            assert_eq!(
+                // left: ( a
+                OperationParser::parse(vec![
+                    get_operator(HirOperator::GroupBegin),
+                    get_string_term("a"),
+                ]),
+                Err(OperationParserError::UnbalancedGroup { token_index: 0 }),
+            );
+
+            assert_eq!(
+                // left: a )
+                OperationParser::parse(vec![
+                    get_string_term("a"),
+                    get_operator(HirOperator::GroupEnd),
+                ]),
+                Err(OperationParserError::UnbalancedGroup { token_index: 1 }),
+            );
+        }
+
+        it "treats self literal as path segment" {
+            assert_eq!(
+                // left: self :: a
+                OperationParser::parse(vec![
+                    HirOperationToken::Term(HirExpression::from(HirExpressionKind::Literal(HirLiteral::SelfValue))),
+                    get_operator(HirOperator::Path),
+                    HirOperationToken::Term(HirExpression::from(HirExpressionKind::Identifier("a".into()))),
+                ]),
+                Ok(get_operation_expression(HirOperation::Path(HirPath::Unresolved(vec!["self".into(), "a".into()])))),
+            );
+        }
+
+        it "rejects invalid kind of path segment" {
+            assert_eq!(
+                // left: "a" :: true
+                OperationParser::parse(vec![
+                    get_string_term("a"),
+                    get_operator(HirOperator::Path),
+                    HirOperationToken::Term(HirExpression::from(HirExpressionKind::Literal(HirLiteral::Boolean(true)))),
+                ]),
+                Err(OperationParserError::InvalidKindOfTerm { token_index: 1 }),
+            );
+        }
+    }
+
+    describe "operator table" {
+        it "lists longer symbols first" {
+            let symbols = OperatorDefinition::symbols(OperatorFixity::Infix);
+            let position = |symbol: &str| symbols.iter().position(|v| *v == symbol).unwrap();
+
+            assert!(position("==") < position("="));
+            assert!(position("<<") < position("<"));
+            assert!(position("&&") < position("&"));
+        }
+
+        it "does not define the same symbol twice in a fixity" {
+            for each_definition in OPERATORS {
+                if let Some(symbol) = each_definition.symbol {
+                    let count = OPERATORS.iter().filter(|v| v.symbol == Some(symbol) && v.fixity == each_definition.fixity).count();
+                    assert_eq!(count, 1, "symbol `{}` is defined more than once", symbol);
+                }
+            }
+        }
+
+        it "finds operator by symbol and fixity" {
+            assert_eq!(OperatorDefinition::find_by_symbol("-", OperatorFixity::Infix).map(|v| v.operator.clone()), Some(HirOperator::Subtract));
+            assert_eq!(OperatorDefinition::find_by_symbol("-", OperatorFixity::Prefix).map(|v| v.operator.clone()), Some(HirOperator::Negative));
+        }
+
+        it "binds bitwise operators looser than comparisons in JS" {
+            let js_precedence = |operator: HirOperator| OperatorDefinition::find(&operator).and_then(|v| v.js_precedence).unwrap();
+
+            assert!(js_precedence(HirOperator::BitAnd) < js_precedence(HirOperator::Equal));
+            assert!(js_precedence(HirOperator::BitOr) < js_precedence(HirOperator::LessThan));
+            assert_eq!(OperatorDefinition::find_by_js_symbol("===", OperatorFixity::Infix).map(|v| v.operator.clone()), Some(HirOperator::Equal));
+        }
+
+        it "finds function call operator regardless of arguments" {
+            let definition = OperatorDefinition::find(&HirOperator::FunctionCall(vec![get_string_expression("a")]));
+            assert_eq!(definition.map(|v| v.fixity), Some(OperatorFixity::Postfix));
+        }
+    }
 }
@@ -43,8 +43,97 @@ speculate!{
             expect_success("\nfn f() {}\nfn f() {}\n", "Main::main");
         }
 
-        it "rejects semicolon separator" {
-            expect_unmatch_failure(";", "Main::main");
+        it "recovers invalid item" {
+            expect_success_eq(";", "Main::main", tree!(
+                node!("Main::main" => [
+                    node!("Item::item" => [
+                        error!("invalid_item", [leaf!(";")]),
+                    ]),
+                ])
+            ));
+        }
+
+        it "skips invalid item to the next item" {
+            expect_success_eq("fn f( {}\nfn g() {}", "Main::main", tree!(
+                node!("Main::main" => [
+                    node!("Item::item" => [
+                        error!("invalid_item", [leaf!("fn f( {}")]),
+                    ]),
+                    node!("Item::item" => [
+                        node!("Function::function" => [
+                            node!("Main::accessibility" => []),
+                            node!("Identifier::identifier" => [leaf!("g")]),
+                            node!("args" => []),
+                            node!("exprs" => []),
+                        ]),
+                    ]),
+                ])
+            ));
+        }
+    }
+
+    describe "windows source" {
+        it "accepts CRLF line endings" {
+            expect_success("fn f() {}\r\nfn g() {}\r\n", "Main::main");
+            expect_success("fn f() {\r\n    0\r\n    1\r\n}\r\n", "Main::main");
+        }
+
+        it "keeps expressions separated by CRLF" {
+            expect_success_eq("fn f() {\r\n0\r\n1\r\n}", "Main::main", tree!(
+                node!("Main::main" => [
+                    node!("Item::item" => [
+                        node!("Function::function" => [
+                            node!("Main::accessibility" => []),
+                            node!("Identifier::identifier" => [leaf!("f")]),
+                            node!("args" => []),
+                            node!("exprs" => [
+                                node!("Expression::expression" => [
+                                    node!("Literal::literal" => [
+                                        node!("Literal::number" => [
+                                            node!("value" => [
+                                                node!("Literal::decimal_number" => [leaf!("0")]),
+                                            ]),
+                                        ]),
+                                    ]),
+                                ]),
+                                node!("Expression::expression" => [
+                                    node!("Literal::literal" => [
+                                        node!("Literal::number" => [
+                                            node!("value" => [
+                                                node!("Literal::decimal_number" => [leaf!("1")]),
+                                            ]),
+                                        ]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ]),
+                ])
+            ));
+        }
+
+        it "ends comments before CRLF" {
+            expect_success("// comment\r\nfn f() {}", "Main::main");
+            expect_success_eq("/// doc\r\nfn f() {}", "Item::item", tree!(
+                node!("Item::item" => [
+                    node!("Item::documentation" => [leaf!("/// doc")]),
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ])
+            ));
+        }
+
+        it "skips leading byte order mark" {
+            expect_success("\u{FEFF}fn f() {}", "Main::main");
+            expect_success("\u{FEFF}", "Main::main");
+        }
+
+        it "accepts unicode whitespace" {
+            expect_success("fn\u{3000}f()\u{00A0}{}", "Main::main");
         }
     }
 
@@ -92,6 +181,12 @@ speculate!{
                 ])
             ));
         }
+
+        it "checks identifier with shared grammar" {
+            assert!(Syntax::is_identifier("fn_"));
+            assert!(!Syntax::is_identifier("fn"));
+            assert!(!Syntax::is_identifier("a-b"));
+        }
     }
 
     describe "symbol" {
@@ -109,6 +204,35 @@ speculate!{
                 expect_success(";", "Symbol::expression_separator");
                 expect_success("  \n  ", "Symbol::expression_separator");
             }
+
+            it "accepts comments before separator" {
+                expect_success(" // comment\n", "Symbol::expression_separator");
+                expect_success(" /* comment */;", "Symbol::expression_separator");
+            }
+        }
+
+        describe "comment" {
+            it "matches line comment until newline" {
+                expect_success("// comment", "Symbol::line_comment");
+                expect_success("//// comment", "Symbol::line_comment");
+                expect_failure("// comment\n", "Symbol::line_comment");
+            }
+
+            it "does not match doc comment as line comment" {
+                expect_failure("/// doc", "Symbol::line_comment");
+                expect_success("/// doc", "Symbol::doc_comment");
+            }
+
+            it "matches nested block comment" {
+                expect_success("/* comment */", "Symbol::block_comment");
+                expect_success("/* a /* b */ c */", "Symbol::block_comment");
+                expect_failure("/* a /* b */", "Symbol::block_comment");
+            }
+
+            it "treats comments as whitespace" {
+                expect_success("// comment\nfn f() {}\n/* comment */", "Main::main");
+                expect_success("fn f() {\n    0 // comment\n    /* comment */ 1\n}", "Main::main");
+            }
         }
     }
 }
@@ -80,6 +80,14 @@ speculate!{
             ));
         }
 
+        it "matches hako as path root" {
+            expect_success_eq("hako", "Expression::expression", tree!(
+                node!("Expression::expression" => [
+                    node!("Expression::path_root" => [leaf!("hako")]),
+                ])
+            ));
+        }
+
         it "matches data type" {
             expect_success_eq("usize", "Expression::expression", tree!(
                 node!("Expression::expression" => [
@@ -180,6 +188,57 @@ speculate!{
                     ])
                 ));
             }
+            it "matches longer symbol first" {
+                expect_success_eq("0 <= 1", "Operation::operation", tree!(
+                    node!("Operation::operation" => [
+                        node!("Expression::pure_expression" => [
+                            node!("Literal::literal" => [
+                                node!("Literal::number" => [
+                                    node!("value" => [
+                                        node!("Literal::decimal_number" => [leaf!("0")]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                        node!("operator" => [leaf!("<=")]),
+                        node!("Expression::pure_expression" => [
+                            node!("Literal::literal" => [
+                                node!("Literal::number" => [
+                                    node!("value" => [
+                                        node!("Literal::decimal_number" => [leaf!("1")]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ])
+                ));
+            }
+
+            it "does not split infix operator into postfix and infix operator" {
+                expect_success_eq("0 != 1", "Operation::operation", tree!(
+                    node!("Operation::operation" => [
+                        node!("Expression::pure_expression" => [
+                            node!("Literal::literal" => [
+                                node!("Literal::number" => [
+                                    node!("value" => [
+                                        node!("Literal::decimal_number" => [leaf!("0")]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                        node!("operator" => [leaf!("!=")]),
+                        node!("Expression::pure_expression" => [
+                            node!("Literal::literal" => [
+                                node!("Literal::number" => [
+                                    node!("value" => [
+                                        node!("Literal::decimal_number" => [leaf!("1")]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ])
+                ));
+            }
         }
 
         describe "prefix/postfix operator" {
@@ -543,7 +602,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::binary_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -569,7 +628,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::octal_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -593,7 +652,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::hexadecimal_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -617,7 +676,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::decimal_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -679,7 +738,7 @@ speculate!{
                             node!("Literal::number_exponent" => [
                                 leaf!("+"),
                                 node!("value" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -705,7 +764,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::decimal_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -715,7 +774,7 @@ speculate!{
                         node!("Literal::number" => [
                             node!("value" => [
                                 node!("Literal::decimal_number" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("1_")]),
                                 ]),
                             ]),
                         ])
@@ -766,13 +825,13 @@ speculate!{
                             node!("value" => [
                                 node!("Literal::hexadecimal_number" => [
                                     error!("digit_separator_on_side", [
-                                        leaf!("00A"),
+                                        leaf!("_00A"),
                                     ]),
                                     error!("starts_with_zero", [
-                                        leaf!("00A"),
+                                        leaf!("_00A"),
                                     ]),
                                     error!("has_capital_letter", [
-                                        leaf!("00A"),
+                                        leaf!("_00A"),
                                     ]),
                                 ]),
                             ]),
@@ -843,7 +902,7 @@ speculate!{
                     node!("Literal::number" => [
                         node!("Literal::float_number" => [
                             node!("integer" => [
-                                error!("digit_separator_on_side", [leaf!("0")]),
+                                error!("digit_separator_on_side", [leaf!("_0")]),
                             ]),
                             node!("float" => [leaf!("0")]),
                         ]),
@@ -857,7 +916,7 @@ speculate!{
                         node!("Literal::float_number" => [
                             node!("integer" => [leaf!("0")]),
                             node!("float" => [
-                                error!("digit_separator_on_side", [leaf!("0")]),
+                                error!("digit_separator_on_side", [leaf!("_0")]),
                             ]),
                         ]),
                     ])
@@ -882,7 +941,7 @@ speculate!{
                             node!("Literal::float_number" => [
                                 node!("integer" => [leaf!("0")]),
                                 node!("float" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("_1")]),
                                 ]),
                             ]),
                         ])
@@ -893,7 +952,7 @@ speculate!{
                             node!("Literal::float_number" => [
                                 node!("integer" => [leaf!("0")]),
                                 node!("float" => [
-                                    error!("digit_separator_on_side", [leaf!("1")]),
+                                    error!("digit_separator_on_side", [leaf!("1_")]),
                                 ]),
                             ]),
                         ])
@@ -921,8 +980,8 @@ speculate!{
                             node!("Literal::float_number" => [
                                 node!("integer" => [leaf!("0")]),
                                 node!("float" => [
-                                    error!("digit_separator_on_side", [leaf!("10")]),
-                                    error!("ends_with_zero", [leaf!("10")]),
+                                    error!("digit_separator_on_side", [leaf!("_10")]),
+                                    error!("ends_with_zero", [leaf!("_10")]),
                                 ]),
                             ]),
                         ])
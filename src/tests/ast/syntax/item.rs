@@ -57,6 +57,33 @@ speculate!{
                 ])
             ));
         }
+
+        it "attaches doc comments" {
+            expect_success_eq("/// a\n/// b\nfn f() {}", "Item::item", tree!(
+                node!("Item::item" => [
+                    node!("Item::documentation" => [
+                        leaf!("/// a"),
+                        leaf!("/// b"),
+                    ]),
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => []),
+                    ]),
+                ])
+            ));
+        }
+
+        it "reports doc comment which no item follows" {
+            expect_success_eq("/// a", "Item::item", tree!(
+                node!("Item::item" => [
+                    error!("stray_doc_comment", [leaf!("/// a")]),
+                ])
+            ));
+
+            expect_success("fn f() {}\n/// a", "Main::main");
+        }
     }
 
     describe "use declaration" {
@@ -77,6 +104,55 @@ speculate!{
                 ])
             ));
         }
+
+        it "matches super segments" {
+            expect_success_eq("use super::super::a", "UseDeclaration::use_declaration", tree!(
+                node!("UseDeclaration::use_declaration" => [
+                    leaf!("super"),
+                    leaf!("super"),
+                    leaf!("a"),
+                ])
+            ));
+        }
+
+        it "matches grouped paths" {
+            expect_success_eq("use a::{b, c::d}", "UseDeclaration::use_declaration", tree!(
+                node!("UseDeclaration::use_declaration" => [
+                    leaf!("a"),
+                    node!("UseDeclaration::group" => [
+                        node!("UseDeclaration::use_tree" => [leaf!("b")]),
+                        node!("UseDeclaration::use_tree" => [leaf!("c"), leaf!("d")]),
+                    ]),
+                ])
+            ));
+        }
+
+        it "matches glob" {
+            expect_success_eq("use a::*", "UseDeclaration::use_declaration", tree!(
+                node!("UseDeclaration::use_declaration" => [
+                    leaf!("a"),
+                    node!("UseDeclaration::glob" => []),
+                ])
+            ));
+        }
+
+        it "matches alias" {
+            expect_success_eq("use a::b as c", "UseDeclaration::use_declaration", tree!(
+                node!("UseDeclaration::use_declaration" => [
+                    leaf!("a"),
+                    leaf!("b"),
+                    node!("UseDeclaration::alias" => [leaf!("c")]),
+                ])
+            ));
+        }
+
+        it "nests groups, globs and aliases" {
+            expect_success("use a::{b::{c, d as e}, f::*}", "UseDeclaration::use_declaration");
+        }
+
+        it "does not match empty group" {
+            expect_failure("use a::{}", "UseDeclaration::use_declaration");
+        }
     }
 
     describe "function" {
@@ -312,6 +388,43 @@ speculate!{
             it "allows separators and whitespaces around expression" {
                 expect_success("fn f() { ;\n0 ;\n}", "Function::function");
             }
+
+            it "reports doc comment in body" {
+                expect_success_eq("fn f() {\n/// a\n0\n}", "Function::function", tree!(
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => [
+                            error!("stray_doc_comment", [leaf!("/// a")]),
+                            node!("Expression::expression" => [
+                                node!("Literal::literal" => [
+                                    node!("Literal::number" => [
+                                        node!("value" => [
+                                            node!("Literal::decimal_number" => [
+                                                leaf!("0"),
+                                            ]),
+                                        ]),
+                                    ]),
+                                ]),
+                            ]),
+                        ]),
+                    ])
+                ));
+            }
+
+            it "recovers invalid body until closing brace" {
+                expect_success_eq("fn f() {0 +}", "Function::function", tree!(
+                    node!("Function::function" => [
+                        node!("Main::accessibility" => []),
+                        node!("Identifier::identifier" => [leaf!("f")]),
+                        node!("args" => []),
+                        node!("exprs" => [
+                            error!("invalid_function_body", [leaf!("0 +")]),
+                        ]),
+                    ])
+                ));
+            }
         }
 
         describe "return type" {
@@ -0,0 +1,165 @@
+use std::{fs, path::PathBuf};
+use speculate::speculate;
+
+use crate::cli::*;
+use crate::diagnostic::message::DiagnosticLanguage;
+use crate::tests::TempDir;
+
+fn arguments(v: &[&str]) -> Vec<String> {
+    v.iter().map(|v| v.to_string()).collect()
+}
+
+fn run(v: &[&str]) -> (i32, String, String) {
+    let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    let status = Karinc::new(&mut stdout, &mut stderr).with_language(DiagnosticLanguage::English).run(&arguments(v));
+    (status, String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap())
+}
+
+speculate!{
+    describe "arguments" {
+        it "parses command, options and inputs" {
+            assert_eq!(
+                KarincCommand::parse(&arguments(&["build", "-o", "out", "--error-format=json", "a.kr", "src"])),
+                Ok(KarincCommand::Compile(KarincOptions {
+                    subcommand: KarincSubcommand::Build,
+                    inputs: vec![PathBuf::from("a.kr"), PathBuf::from("src")],
+                    output: Some(PathBuf::from("out")),
+                    error_format: KarincErrorFormat::Json,
+                    color: KarincColor::Auto,
+                    checked_arithmetic: false,
+                })),
+            );
+        }
+
+        it "parses explain" {
+            assert_eq!(KarincCommand::parse(&arguments(&["--explain", "K0001"])), Ok(KarincCommand::Explain("K0001".to_string())));
+        }
+
+        it "rejects invalid arguments" {
+            assert_eq!(KarincCommand::parse(&arguments(&[])), Err(KarincArgumentError::MissingCommand));
+            assert_eq!(KarincCommand::parse(&arguments(&["run", "a.kr"])), Err(KarincArgumentError::UnknownCommand("run".to_string())));
+            assert_eq!(KarincCommand::parse(&arguments(&["check"])), Err(KarincArgumentError::MissingInput));
+            assert_eq!(KarincCommand::parse(&arguments(&["check", "-x"])), Err(KarincArgumentError::UnknownOption("-x".to_string())));
+            assert_eq!(KarincCommand::parse(&arguments(&["check", "-o"])), Err(KarincArgumentError::MissingValue("-o".to_string())));
+            assert_eq!(
+                KarincCommand::parse(&arguments(&["check", "--color", "sometimes"])),
+                Err(KarincArgumentError::InvalidValue { option: "--color".to_string(), value: "sometimes".to_string() }),
+            );
+        }
+    }
+
+    describe "exit status" {
+        it "explains error code" {
+            let (status, stdout, _) = run(&["--explain", "k0006"]);
+            assert_eq!(status, EXIT_SUCCESS);
+            assert!(stdout.starts_with("K0006: "));
+
+            assert_eq!(run(&["--explain", "K9999"]).0, EXIT_USAGE_ERROR);
+        }
+
+        it "reports usage error" {
+            assert_eq!(run(&["check"]).0, EXIT_USAGE_ERROR);
+            assert_eq!(run(&["check", "not-found.kr"]).0, EXIT_USAGE_ERROR);
+        }
+
+        it "reports compile error with rendered diagnostics" {
+            let dir = TempDir::new("cli-error");
+            let input = dir.join("main.kr");
+            fs::write(&input, "fn main() {\n    0 < 1 < 2\n}").unwrap();
+
+            let (status, _, stderr) = run(&["check", "--color=never", input.to_str().unwrap()]);
+            assert_eq!(status, EXIT_COMPILE_ERROR);
+            assert!(stderr.contains("error[K0006]"));
+            assert!(stderr.contains("main.kr:2:"));
+        }
+    }
+
+    describe "build" {
+        it "writes javascript next to source" {
+            let dir = TempDir::new("cli-build");
+            let input = dir.join("main.kr");
+            fs::write(&input, "fn main(){}").unwrap();
+
+            assert_eq!(run(&["build", input.to_str().unwrap()]).0, EXIT_SUCCESS);
+            assert_eq!(fs::read_to_string(dir.join("main.js")).unwrap(), "function i_2(){}");
+        }
+
+        it "compiles hako directory into one output" {
+            let dir = TempDir::new("cli-build-hako");
+            fs::create_dir_all(dir.join("h/a")).unwrap();
+            fs::write(dir.join("h/a.kr"), "fn f(){}").unwrap();
+            fs::write(dir.join("h/a/b.kr"), "fn g(){}").unwrap();
+            fs::write(dir.join("h/readme.txt"), "").unwrap();
+
+            let (input, output) = (dir.join("h"), dir.join("out.js"));
+            assert_eq!(run(&["build", "-o", output.to_str().unwrap(), input.to_str().unwrap()]).0, EXIT_SUCCESS);
+            assert_eq!(fs::read_to_string(output).unwrap().matches("function").count(), 2);
+        }
+
+        it "compiles hako declared by manifest" {
+            let dir = TempDir::new("cli-build-manifest");
+            fs::create_dir_all(dir.join("app/src")).unwrap();
+            fs::write(dir.join("app/hako.toml"), "[hako]\nname = \"app\"\nversion = \"0.1.0\"\n").unwrap();
+            fs::write(dir.join("app/src/main.kr"), "fn main(){}").unwrap();
+
+            let manifest = dir.join("app/hako.toml");
+            assert_eq!(run(&["build", manifest.to_str().unwrap()]).0, EXIT_SUCCESS);
+            assert!(dir.join("app.js").is_file());
+        }
+
+        it "reports invalid manifest" {
+            let dir = TempDir::new("cli-invalid-manifest");
+            fs::write(dir.join("hako.toml"), "[hako]\nname = \"app\"\nversion = \"1\"\n").unwrap();
+
+            let (status, _, stderr) = run(&["check", "--color=never", dir.to_str().unwrap()]);
+            assert_eq!(status, EXIT_COMPILE_ERROR);
+            assert!(stderr.contains("invalid version `1`"));
+        }
+
+        it "compiles dependencies of hako" {
+            let dir = TempDir::new("cli-build-dependencies");
+            fs::create_dir_all(dir.join("app/src")).unwrap();
+            fs::create_dir_all(dir.join("util/src")).unwrap();
+            fs::write(dir.join("app/hako.toml"), "[hako]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nutil = { path = \"../util\" }\n").unwrap();
+            fs::write(dir.join("util/hako.toml"), "[hako]\nname = \"util\"\nversion = \"0.1.0\"\n").unwrap();
+            fs::write(dir.join("app/src/main.kr"), "fn main(){}").unwrap();
+            fs::write(dir.join("util/src/main.kr"), "fn f(){}").unwrap();
+
+            let (status, stdout, _) = run(&["emit-js", dir.join("app").to_str().unwrap()]);
+            assert_eq!(status, EXIT_SUCCESS);
+            assert_eq!(stdout.matches("function").count(), 2);
+        }
+
+        it "reports dependency cycle" {
+            let dir = TempDir::new("cli-dependency-cycle");
+            fs::create_dir_all(dir.join("a")).unwrap();
+            fs::create_dir_all(dir.join("b")).unwrap();
+            fs::write(dir.join("a/hako.toml"), "[hako]\nname = \"a\"\nversion = \"0.1.0\"\n\n[dependencies]\nb = { path = \"../b\" }\n").unwrap();
+            fs::write(dir.join("b/hako.toml"), "[hako]\nname = \"b\"\nversion = \"0.1.0\"\n\n[dependencies]\na = { path = \"../a\" }\n").unwrap();
+
+            let (status, _, stderr) = run(&["check", "--color=never", dir.join("a").to_str().unwrap()]);
+            assert_eq!(status, EXIT_COMPILE_ERROR);
+            assert!(stderr.contains("K0017"));
+            assert!(stderr.contains("a -> b -> a"));
+        }
+
+        it "prints javascript by emit-js" {
+            let dir = TempDir::new("cli-emit-js");
+            let input = dir.join("main.kr");
+            fs::write(&input, "fn main(){}").unwrap();
+
+            assert_eq!(run(&["emit-js", input.to_str().unwrap()]), (EXIT_SUCCESS, "function i_2(){}".to_string(), String::new()));
+        }
+
+        it "writes json diagnostics apart from emitted code" {
+            let dir = TempDir::new("cli-emit-js-json");
+            let input = dir.join("main.kr");
+            fs::write(&input, "use main::main as m\nfn f(){}").unwrap();
+
+            let (status, stdout, stderr) = run(&["emit-js", "--error-format=json", input.to_str().unwrap()]);
+            assert_eq!(status, EXIT_SUCCESS);
+            assert_eq!(stdout, "function i_3(){}");
+            assert!(stderr.contains("\"K0022\""));
+        }
+    }
+}
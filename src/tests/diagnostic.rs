@@ -0,0 +1,177 @@
+mod code;
+mod fix;
+mod json;
+mod message;
+mod render;
+
+use speculate::speculate;
+use volt::parser::ParserError;
+
+use crate::ast::operator::OperationParserError;
+use crate::diagnostic::{*, code::*, message::DiagnosticLanguage};
+use crate::hako::{graph::HakoGraphError, loader::HakoLoaderError, manifest::HakoManifestError};
+use crate::hir::hirify::{TreeHirifierError, TreeHirifierLog};
+use crate::hir::type_check::DataTypeError;
+use crate::span::Span;
+
+speculate!{
+    describe "conversion" {
+        it "converts parser error without label" {
+            let diagnostic: Diagnostic = ParserError::NoMatchedRule.into();
+            assert!(diagnostic.is_error());
+            assert_eq!(diagnostic.primary_label, None);
+        }
+
+        it "converts hirifier log with its span" {
+            let diagnostic: Diagnostic = TreeHirifierLog::Error(
+                TreeHirifierError::InvalidOperation {
+                    error: OperationParserError::NonAssociativeChain { token_index: 3 },
+                    span: Span::default(),
+                },
+            ).into();
+
+            assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::NonAssociativeChain));
+            assert!(diagnostic.primary_label.is_some());
+            assert!(diagnostic.help.is_some());
+        }
+
+        it "converts literal error with machine-applicable fix" {
+            let diagnostic: Diagnostic = TreeHirifierError::InvalidSyntax {
+                name: "starts_with_zero".to_string(),
+                source: "012".to_string(),
+                span: Span::default(),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::StartsWithZero));
+            assert_eq!(
+                diagnostic.suggestions,
+                vec![
+                    DiagnosticSuggestion {
+                        message: "remove the leading zero".to_string(),
+                        span: Span::default(),
+                        replacement: "12".to_string(),
+                        applicability: DiagnosticApplicability::MachineApplicable,
+                    },
+                ],
+            );
+        }
+
+        it "marks fixes over the same span as maybe incorrect" {
+            let literal_error = |name: &str| -> Diagnostic {
+                TreeHirifierError::InvalidSyntax {
+                    name: name.to_string(),
+                    source: "_01_".to_string(),
+                    span: Span::default(),
+                }.into()
+            };
+
+            let mut diagnostics = vec![literal_error("digit_separator_on_side"), literal_error("starts_with_zero")];
+            Diagnostic::mark_conflicting_suggestions(&mut diagnostics);
+
+            assert!(diagnostics.iter().all(|v| v.suggestions[0].applicability == DiagnosticApplicability::MaybeIncorrect));
+
+            let mut diagnostics = vec![literal_error("starts_with_zero")];
+            Diagnostic::mark_conflicting_suggestions(&mut diagnostics);
+
+            assert_eq!(diagnostics[0].suggestions[0].applicability, DiagnosticApplicability::MachineApplicable);
+        }
+
+        it "converts recovered syntax error without fix" {
+            let diagnostic: Diagnostic = TreeHirifierError::InvalidSyntax {
+                name: "invalid_item".to_string(),
+                source: "fn".to_string(),
+                span: Span::default(),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::InvalidItem));
+            assert_eq!(diagnostic.suggestions, Vec::new());
+        }
+
+        it "converts data type error with its span" {
+            let diagnostic: Diagnostic = DataTypeError::UnknownIdentifier { span: Span::default() }.into();
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::UnknownIdentifier));
+            assert!(diagnostic.primary_label.is_some());
+        }
+
+        it "converts loader errors with codes" {
+            let diagnostic: Diagnostic = HakoLoaderError::Io {
+                path: "src".into(),
+                message: "not found".to_string(),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::FileReadFailed));
+            assert_eq!(diagnostic.message, "failed to read `src`: not found");
+
+            let diagnostic: Diagnostic = HakoLoaderError::MissingEntry {
+                path: "src".into(),
+                entry: "main".to_string(),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::MissingEntryModule));
+            assert_eq!(diagnostic.message, "entry module `main` is not found in `src`");
+            assert!(diagnostic.help.is_some());
+        }
+
+        it "converts manifest errors with codes" {
+            let diagnostic: Diagnostic = HakoLoaderError::Manifest(HakoManifestError::InvalidVersion {
+                path: "hako.toml".into(),
+                version: "1".to_string(),
+            }).into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::InvalidHakoVersion));
+            assert_eq!(diagnostic.message, "invalid version `1` in `hako.toml`: expected `major.minor.patch`");
+
+            let diagnostic: Diagnostic = HakoManifestError::InvalidFormat {
+                path: "hako.toml".into(),
+                message: "unknown field `edition`".to_string(),
+                span: Some(Span::default()),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::InvalidManifest));
+            assert!(diagnostic.primary_label.is_some());
+        }
+
+        it "converts graph errors with codes" {
+            let diagnostic: Diagnostic = HakoGraphError::DependencyNameMismatch {
+                path: "util/hako.toml".into(),
+                expected: "utils".to_string(),
+                found: "util".to_string(),
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::DependencyNameMismatch));
+            assert_eq!(diagnostic.message, "dependency `utils` refers to hako `util` in `util/hako.toml`");
+
+            let diagnostic: Diagnostic = HakoGraphError::DuplicateName {
+                name: "util".to_string(),
+                paths: vec!["a".into(), "b".into()],
+            }.into();
+
+            assert_eq!(diagnostic.code, Some(DiagnosticCode::DuplicateHakoName));
+            assert_eq!(diagnostic.message, "hako name `util` is used by multiple hakos: `a`, `b`");
+        }
+    }
+
+    describe "localization" {
+        it "keeps label, help and notes which catalog does not define" {
+            let diagnostic = Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::FileReadFailed, vec!["a.kr".to_string(), "denied".to_string()], None)
+                .with_primary_label(Span::default(), Some("read here".to_string()))
+                .with_help("check the permission".to_string())
+                .with_note("in `src`".to_string())
+                .localize(DiagnosticLanguage::Japanese);
+
+            assert_eq!(diagnostic.message, "`a.kr` を読み込めませんでした: denied");
+            assert_eq!(diagnostic.primary_label.unwrap().message, Some("read here".to_string()));
+            assert_eq!(diagnostic.help, Some("check the permission".to_string()));
+            assert_eq!(diagnostic.notes, vec!["in `src`".to_string()]);
+        }
+
+        it "replaces label and help which catalog defines" {
+            let diagnostic = Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::NonAssociativeChain, Vec::new(), Some(Span::default()))
+                .localize(DiagnosticLanguage::Japanese);
+
+            assert_eq!(diagnostic.primary_label.unwrap().message, Some("連鎖した演算子".to_string()));
+            assert_eq!(diagnostic.help, Some("比較を `&&` で区切るか括弧を使用してください".to_string()));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+use speculate::speculate;
+
+use crate::diagnostic::code::*;
+
+speculate!{
+    describe "diagnostic code" {
+        it "gives unique code to each variant" {
+            let codes: HashSet<&str> = DiagnosticCode::ALL.iter().map(|v| v.code()).collect();
+            assert_eq!(codes.len(), DiagnosticCode::ALL.len());
+        }
+
+        it "keeps code format" {
+            for each_code in DiagnosticCode::ALL {
+                let code = each_code.code();
+                assert!(code.len() == 5 && code.starts_with('K') && code[1..].chars().all(|v| v.is_ascii_digit()), "{}", code);
+            }
+        }
+
+        it "keeps assigned codes" {
+            assert_eq!(DiagnosticCode::NoMatchedRule.code(), "K0001");
+            assert_eq!(DiagnosticCode::UnknownIdentifier.code(), "K0008");
+            assert_eq!(DiagnosticCode::EndsWithZero.code(), "K0013");
+        }
+
+        it "finds variant by code" {
+            for each_code in DiagnosticCode::ALL {
+                assert_eq!(DiagnosticCode::from_code(each_code.code()), Some(*each_code));
+            }
+
+            assert_eq!(DiagnosticCode::from_code("k0010"), Some(DiagnosticCode::StartsWithZero));
+            assert_eq!(DiagnosticCode::from_code("K9999"), None);
+        }
+
+        it "finds variant by syntax error name" {
+            assert_eq!(DiagnosticCode::from_syntax_error_name("has_capital_letter"), Some(DiagnosticCode::HasCapitalLetter));
+            assert_eq!(DiagnosticCode::from_syntax_error_name("unknown"), None);
+        }
+    }
+
+    describe "explanation" {
+        it "has example for every code" {
+            for each_code in DiagnosticCode::ALL {
+                assert!(each_code.explanation().contains("example"), "{}", each_code);
+            }
+        }
+
+        it "explains code" {
+            let explanation = DiagnosticCode::explain("K0006").unwrap();
+            assert!(explanation.starts_with("K0006: "));
+            assert!(explanation.contains("0 < a && a < 10"));
+            assert_eq!(DiagnosticCode::explain("K9999"), None);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use speculate::speculate;
+
+use crate::diagnostic::{code::*, fix::*};
+
+speculate!{
+    describe "literal fix" {
+        it "removes digit separators on the side" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::DigitSeparatorOnSide, "_1_000_"), Some("1_000".to_string()));
+        }
+
+        it "removes leading zeros" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::StartsWithZero, "012"), Some("12".to_string()));
+            assert_eq!(fix_syntax_error(DiagnosticCode::StartsWithZero, "0_0_1"), Some("1".to_string()));
+            assert_eq!(fix_syntax_error(DiagnosticCode::StartsWithZero, "00"), Some("0".to_string()));
+        }
+
+        it "writes hexadecimal digits in lowercase" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::HasCapitalLetter, "fF"), Some("ff".to_string()));
+        }
+
+        it "drops explicit plus symbol" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::ExplicitPlusSymbol, "e+"), Some("e".to_string()));
+        }
+
+        it "removes trailing zeros" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::EndsWithZero, "50"), Some("5".to_string()));
+            assert_eq!(fix_syntax_error(DiagnosticCode::EndsWithZero, "0_0"), Some("0".to_string()));
+        }
+
+        it "does not fix other errors" {
+            assert_eq!(fix_syntax_error(DiagnosticCode::InvalidItem, "fn"), None);
+            assert_eq!(fix_syntax_error(DiagnosticCode::StartsWithZero, "0"), None);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use serde_json::Value;
+use speculate::speculate;
+
+use crate::diagnostic::{*, code::*, json::*};
+use crate::span::*;
+
+fn fixture(content: &str) -> Value {
+    serde_json::from_str(content).expect("fixture must be valid JSON")
+}
+
+speculate!{
+    before {
+        let mut source_map = SourceMap::new();
+        let file_id = source_map.add("main.kr".to_string(), "fn f() {\n    012\n}".to_string());
+
+        #[allow(unused)]
+        let new_span = |index: usize, line: usize, start: usize, end: usize| Span::new(
+            file_id,
+            SourcePosition::new(index, line, start),
+            SourcePosition::new(index + end - start, line, end),
+        );
+    }
+
+    describe "json writer" {
+        it "writes unlocated diagnostic with null location" {
+            let diagnostic = Diagnostic::error("input does not match any syntax rule".to_string());
+            let output = DiagnosticJsonWriter::new(&source_map).write(&[diagnostic]);
+
+            assert_eq!(
+                serde_json::from_str::<Value>(&output).unwrap(),
+                fixture(include_str!("fixtures/unlocated.json")),
+            );
+        }
+
+        it "writes locations, related labels and suggestions" {
+            let diagnostics = vec![
+                Diagnostic::error("decimal integer cannot start with zero".to_string())
+                    .with_code(DiagnosticCode::StartsWithZero)
+                    .with_primary_label(new_span(13, 1, 4, 7), Some("leading zero".to_string()))
+                    .with_secondary_label(new_span(3, 0, 3, 4), Some("in this function".to_string()))
+                    .with_note("octal numbers use `0o` prefix".to_string())
+                    .with_suggestion("remove the leading zero".to_string(), new_span(13, 1, 4, 7), "12".to_string(), DiagnosticApplicability::MachineApplicable),
+                Diagnostic::warning("unused value".to_string())
+                    .with_help("remove the value".to_string()),
+            ];
+
+            assert_eq!(
+                DiagnosticJsonWriter::new(&source_map).value(&diagnostics),
+                fixture(include_str!("fixtures/located.json")),
+            );
+        }
+
+        it "has schema version" {
+            let output = DiagnosticJsonWriter::new(&source_map).value(&[]);
+            assert_eq!(output["version"], Value::from(DIAGNOSTIC_JSON_SCHEMA_VERSION));
+            assert_eq!(output["diagnostics"], Value::Array(Vec::new()));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use speculate::speculate;
+
+use crate::diagnostic::{code::*, message::*};
+
+fn placeholders(template: &str) -> Vec<String> {
+    let mut placeholders: Vec<String> = (0..10).map(|i| format!("{{{}}}", i)).filter(|v| template.contains(v.as_str())).collect();
+    placeholders.sort();
+    placeholders
+}
+
+speculate!{
+    describe "message catalog" {
+        it "has every code in both languages" {
+            for each_code in DiagnosticCode::ALL {
+                let english = DiagnosticMessage::get(*each_code, DiagnosticLanguage::English);
+                let japanese = DiagnosticMessage::get(*each_code, DiagnosticLanguage::Japanese);
+
+                assert!(!english.message.is_empty(), "{}", each_code);
+                assert!(!japanese.message.is_empty(), "{}", each_code);
+                assert_ne!(english.message, japanese.message, "{} is not translated", each_code);
+                assert_eq!(english.label.is_some(), japanese.label.is_some(), "{}", each_code);
+                assert_eq!(english.help.is_some(), japanese.help.is_some(), "{}", each_code);
+                assert_eq!(english.suggestion.is_some(), japanese.suggestion.is_some(), "{}", each_code);
+                assert_eq!(placeholders(english.message), placeholders(japanese.message), "{}", each_code);
+            }
+        }
+
+        it "formats arguments" {
+            assert_eq!(DiagnosticMessage::format("segment `{0}` of `{1}`", &["a".to_string(), "b".to_string()]), "segment `a` of `b`");
+        }
+    }
+
+    describe "language" {
+        it "is chosen by locale" {
+            assert_eq!(DiagnosticLanguage::from_locale("ja"), Some(DiagnosticLanguage::Japanese));
+            assert_eq!(DiagnosticLanguage::from_locale("ja_JP.UTF-8"), Some(DiagnosticLanguage::Japanese));
+            assert_eq!(DiagnosticLanguage::from_locale("en-US"), Some(DiagnosticLanguage::English));
+            assert_eq!(DiagnosticLanguage::from_locale("C"), Some(DiagnosticLanguage::English));
+            assert_eq!(DiagnosticLanguage::from_locale("fr_FR"), None);
+        }
+
+        it "defaults to English" {
+            assert_eq!(DiagnosticLanguage::default(), DiagnosticLanguage::English);
+        }
+    }
+}
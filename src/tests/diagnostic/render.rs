@@ -0,0 +1,117 @@
+use speculate::speculate;
+
+use crate::diagnostic::{*, code::*, message::*, render::*};
+use crate::span::*;
+
+speculate!{
+    before {
+        let mut source_map = SourceMap::new();
+        let file_id = source_map.add("main.kr".to_string(), "fn f() {\n    0 < 1 < 2\n}".to_string());
+
+        #[allow(unused)]
+        let new_span = |line: usize, start: usize, end: usize| Span::new(
+            file_id,
+            SourcePosition::new(0, line, start),
+            SourcePosition::new(0, line, end),
+        );
+    }
+
+    describe "plain renderer" {
+        it "renders message only" {
+            let diagnostic = Diagnostic::error("input does not match any syntax rule".to_string());
+
+            assert_eq!(
+                DiagnosticRenderer::plain(&source_map).render(&diagnostic),
+                "error: input does not match any syntax rule\n",
+            );
+        }
+
+        it "renders source excerpt with caret" {
+            let diagnostic = Diagnostic::error("non-associative operators cannot be chained".to_string())
+                .with_primary_label(new_span(1, 10, 11), Some("chained operator".to_string()))
+                .with_help("use parentheses".to_string());
+
+            assert_eq!(
+                DiagnosticRenderer::plain(&source_map).render(&diagnostic),
+                concat!(
+                    "error: non-associative operators cannot be chained\n",
+                    " --> main.kr:2:11\n",
+                    "  |\n",
+                    "2 |     0 < 1 < 2\n",
+                    "  |           ^ chained operator\n",
+                    "  |\n",
+                    "  = help: use parentheses\n",
+                ),
+            );
+        }
+
+        it "renders code, secondary labels and notes" {
+            let diagnostic = Diagnostic::warning("unused value".to_string())
+                .with_primary_label(new_span(1, 4, 9), None)
+                .with_secondary_label(new_span(0, 3, 4), Some("in this function".to_string()))
+                .with_note("values are discarded".to_string());
+
+            assert_eq!(
+                DiagnosticRenderer::plain(&source_map).render(&diagnostic),
+                concat!(
+                    "warning: unused value\n",
+                    " --> main.kr:2:5\n",
+                    "  |\n",
+                    "1 | fn f() {\n",
+                    "  |    - in this function\n",
+                    "  |\n",
+                    "2 |     0 < 1 < 2\n",
+                    "  |     ^^^^^\n",
+                    "  |\n",
+                    "  = note: values are discarded\n",
+                ),
+            );
+        }
+
+        it "renders suggestions with replacement" {
+            let diagnostic = Diagnostic::error("decimal integer cannot start with zero".to_string())
+                .with_primary_label(new_span(1, 4, 5), None)
+                .with_suggestion("remove the leading zero".to_string(), new_span(1, 4, 5), "1".to_string(), DiagnosticApplicability::MachineApplicable);
+
+            assert_eq!(
+                DiagnosticRenderer::plain(&source_map).render(&diagnostic),
+                concat!(
+                    "error: decimal integer cannot start with zero\n",
+                    " --> main.kr:2:5\n",
+                    "  |\n",
+                    "2 |     0 < 1 < 2\n",
+                    "  |     ^\n",
+                    "  |\n",
+                    "  = help: remove the leading zero: `1`\n",
+                ),
+            );
+        }
+    }
+
+    describe "localized renderer" {
+        it "renders coded diagnostic in chosen language" {
+            let diagnostic = Diagnostic::coded(DiagnosticSeverity::Error, DiagnosticCode::NonAssociativeChain, Vec::new(), Some(new_span(1, 10, 11)));
+
+            assert_eq!(
+                DiagnosticRenderer::plain(&source_map).with_language(DiagnosticLanguage::Japanese).render(&diagnostic),
+                concat!(
+                    "error[K0006]: 非結合の演算子は連鎖できません\n",
+                    " --> main.kr:2:11\n",
+                    "  |\n",
+                    "2 |     0 < 1 < 2\n",
+                    "  |           ^ 連鎖した演算子\n",
+                    "  |\n",
+                    "  = help: 比較を `&&` で区切るか括弧を使用してください\n",
+                ),
+            );
+        }
+    }
+
+    describe "colored renderer" {
+        it "keeps plain text in colored output" {
+            let diagnostic = Diagnostic::error("message".to_string());
+            let output = DiagnosticRenderer::new(&source_map, true).render(&diagnostic);
+            assert!(output.contains("message"));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+mod graph;
+mod loader;
+mod manifest;
@@ -0,0 +1,80 @@
+use std::{fs, path::PathBuf};
+use speculate::speculate;
+
+use crate::hako::{graph::*, manifest::*};
+use crate::span::SourceMap;
+use crate::tests::TempDir;
+
+// Writes manifests of hakos like `(name, dependencies)` into their own directories.
+fn temp_hakos(name: &str, hakos: &[(&str, &[&str])]) -> TempDir {
+    let path = TempDir::new(&format!("graph-{}", name));
+
+    for (each_name, each_dependencies) in hakos {
+        let dependencies: Vec<String> = each_dependencies.iter().map(|v| format!("{} = {{ path = \"../{}\" }}\n", v, v)).collect();
+        let manifest = format!("[hako]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}", each_name, dependencies.concat());
+        fs::create_dir_all(path.join(each_name)).unwrap();
+        fs::write(path.join(each_name).join(MANIFEST_FILE_NAME), manifest).unwrap();
+    }
+
+    path
+}
+
+fn manifest(name: &str) -> HakoManifest {
+    HakoManifest::parse(&format!("[hako]\nname = \"{}\"\nversion = \"0.1.0\"\n", name), &PathBuf::from(name).join(MANIFEST_FILE_NAME)).unwrap()
+}
+
+fn names(graph: &HakoGraph, order: &[usize]) -> Vec<String> {
+    order.iter().map(|v| graph.nodes[*v].manifest.name.clone()).collect()
+}
+
+speculate!{
+    describe "hako graph" {
+        it "loads dependencies transitively" {
+            let path = temp_hakos("transitive", &[("app", &["util", "log"]), ("util", &["log"]), ("log", &[])]);
+            let graph = HakoGraph::load(&path.join("app"), &mut SourceMap::new()).unwrap();
+
+            assert_eq!(graph.nodes.len(), 3);
+            assert_eq!(graph.nodes[0].manifest.name, "app");
+            assert_eq!(graph.dependency_names(0), vec!["log".to_string(), "util".to_string()]);
+        }
+
+        it "orders dependencies first" {
+            let path = temp_hakos("order", &[("app", &["util"]), ("util", &["log"]), ("log", &[])]);
+            let graph = HakoGraph::load(&path.join("app").join(MANIFEST_FILE_NAME), &mut SourceMap::new()).unwrap();
+
+            assert_eq!(names(&graph, &graph.topological_order().unwrap()), vec!["log", "util", "app"]);
+        }
+
+        it "detects dependency cycle" {
+            let mut graph = HakoGraph::new();
+            let a = graph.add(manifest("a"));
+            let b = graph.add(manifest("b"));
+            let c = graph.add(manifest("c"));
+            graph.add_dependency(a, b);
+            graph.add_dependency(b, c);
+            graph.add_dependency(c, b);
+
+            assert_eq!(
+                graph.topological_order(),
+                Err(HakoGraphError::DependencyCycle { names: vec!["b".to_string(), "c".to_string(), "b".to_string()] }),
+            );
+        }
+
+        it "reports dependency cycle while loading" {
+            let path = temp_hakos("cycle", &[("a", &["b"]), ("b", &["a"])]);
+
+            assert_eq!(
+                HakoGraph::load(&path.join("a"), &mut SourceMap::new()),
+                Err(vec![HakoGraphError::DependencyCycle { names: vec!["a".to_string(), "b".to_string(), "a".to_string()] }]),
+            );
+        }
+
+        it "reports dependency name which differs from hako name" {
+            let path = temp_hakos("mismatch", &[("app", &["util"]), ("util", &[])]);
+            fs::write(path.join("util").join(MANIFEST_FILE_NAME), "[hako]\nname = \"log\"\nversion = \"0.1.0\"\n").unwrap();
+
+            let errors = HakoGraph::load(&path.join("app"), &mut SourceMap::new()).unwrap_err();
+            assert!(matches!(&errors[..], [HakoGraphError::DependencyNameMismatch { expected, found, .. }] if expected == "util" && found == "log"));
+        }
+    }
+}
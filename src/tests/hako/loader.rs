@@ -0,0 +1,114 @@
+use std::{fs, path::Path};
+use speculate::speculate;
+
+use crate::hako::loader::*;
+use crate::span::{FileId, SourceMap};
+use crate::tests::TempDir;
+
+fn temp_hako(name: &str, files: &[(&str, &str)]) -> TempDir {
+    let path = TempDir::new(&format!("loader-{}", name));
+
+    for (each_path, each_content) in files {
+        let file_path = path.join(each_path);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(file_path, each_content).unwrap();
+    }
+
+    path
+}
+
+fn module_ids(modules: &[LoadedModule]) -> Vec<String> {
+    modules.iter().map(|v| v.id.clone()).collect()
+}
+
+speculate!{
+    describe "hako loader" {
+        it "maps files and directories onto modules" {
+            let path = temp_hako("modules", &[
+                ("a.kr", "fn f() {}"),
+                ("a/b.kr", ""),
+                ("c/d.kr", ""),
+                ("readme.md", ""),
+            ]);
+
+            let mut source_map = SourceMap::new();
+            let (hako, errors) = HakoLoader::new(&mut source_map).load("h", &path);
+
+            assert_eq!(errors, Vec::new());
+            assert_eq!(hako.id, "h");
+            assert_eq!(module_ids(&hako.modules), vec!["a", "c"]);
+            assert_eq!(module_ids(&hako.modules[0].submodules), vec!["b"]);
+            assert_eq!(module_ids(&hako.modules[1].submodules), vec!["d"]);
+
+            // Directory without the module file.
+            assert_eq!(hako.modules[1].path, None);
+            assert_eq!(hako.modules[0].path.as_deref(), Some(path.join("a.kr").as_path()));
+        }
+
+        it "gives each module its own file id" {
+            let path = temp_hako("file-ids", &[("a.kr", ""), ("b.kr", "")]);
+            let mut source_map = SourceMap::new();
+            let (hako, _) = HakoLoader::new(&mut source_map).load("h", &path);
+
+            assert_ne!(hako.modules[0].file_id, hako.modules[1].file_id);
+            assert_eq!(source_map.get(hako.modules[1].file_id).unwrap().name, path.join("b.kr").display().to_string());
+        }
+
+        it "builds ast of hako" {
+            let path = temp_hako("ast", &[("a.kr", ""), ("a/b.kr", "")]);
+            let mut source_map = SourceMap::new();
+            let (hako, _) = HakoLoader::new(&mut source_map).load("h", &path);
+            let ast = hako.ast();
+
+            assert_eq!(ast.id, "h");
+            assert_eq!(ast.modules[0].id, "a");
+            assert_eq!(ast.modules[0].submodules[0].id, "b");
+        }
+
+        it "reports invalid ids and skips the modules" {
+            let path = temp_hako("invalid-ids", &[("my-module.kr", ""), ("fn.kr", ""), ("ok.kr", "")]);
+            let mut source_map = SourceMap::new();
+            let (hako, errors) = HakoLoader::new(&mut source_map).load("h", &path);
+
+            assert_eq!(module_ids(&hako.modules), vec!["ok"]);
+            assert_eq!(
+                errors,
+                vec![
+                    HakoLoaderError::InvalidId { path: path.join("fn.kr"), id: "fn".to_string() },
+                    HakoLoaderError::InvalidId { path: path.join("my-module.kr"), id: "my-module".to_string() },
+                ],
+            );
+        }
+
+        it "keeps submodules of unreadable module file" {
+            let path = temp_hako("unreadable", &[("a/b.kr", "")]);
+            // Invalid UTF-8 cannot be read as source.
+            fs::write(path.join("a.kr"), [0xff]).unwrap();
+
+            let mut source_map = SourceMap::new();
+            let (hako, errors) = HakoLoader::new(&mut source_map).load("h", &path);
+
+            assert_eq!(module_ids(&hako.modules), vec!["a"]);
+            assert_eq!(module_ids(&hako.modules[0].submodules), vec!["b"]);
+            assert!(matches!(errors[..], [HakoLoaderError::Io { ref path, .. }] if path.ends_with("a.kr")));
+        }
+
+        it "loads single file as hako" {
+            let path = temp_hako("file", &[("main.kr", "fn main() {}")]);
+            let mut source_map = SourceMap::new();
+            let (hako, errors) = HakoLoader::new(&mut source_map).load_file("main", &path.join("main.kr"));
+
+            assert_eq!(errors, Vec::new());
+            assert_eq!(module_ids(&hako.modules), vec!["main"]);
+            assert_eq!(hako.modules[0].file_id, FileId::from(0));
+        }
+
+        it "reports missing directory" {
+            let mut source_map = SourceMap::new();
+            let (hako, errors) = HakoLoader::new(&mut source_map).load("h", Path::new("/not/found/hako"));
+
+            assert_eq!(hako.modules, Vec::new());
+            assert!(matches!(errors[..], [HakoLoaderError::Io { .. }]));
+        }
+    }
+}
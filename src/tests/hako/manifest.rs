@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+use speculate::speculate;
+
+use crate::hako::manifest::*;
+use crate::span::FileId;
+
+speculate!{
+    before {
+        #[allow(unused)]
+        let path = Path::new("app/hako.toml");
+    }
+
+    describe "manifest" {
+        it "parses every section" {
+            let manifest = HakoManifest::parse(
+                concat!(
+                    "[hako]\n",
+                    "name = \"app\"\n",
+                    "version = \"1.2.3\"\n",
+                    "entry = \"start\"\n",
+                    "source = \"lib\"\n",
+                    "\n",
+                    "[dependencies]\n",
+                    "util = { path = \"../util\" }\n",
+                    "\n",
+                    "[options]\n",
+                    "checked-arithmetic = true\n",
+                ),
+                path,
+            );
+
+            assert_eq!(
+                manifest,
+                Ok(HakoManifest {
+                    name: "app".to_string(),
+                    version: HakoVersion { major: 1, minor: 2, patch: 3 },
+                    entry: "start".to_string(),
+                    source: PathBuf::from("app/lib"),
+                    dependencies: vec![
+                        HakoDependency { name: "util".to_string(), path: PathBuf::from("app/../util") },
+                    ],
+                    options: HakoOptions { checked_arithmetic: true },
+                }),
+            );
+        }
+
+        it "fills default values" {
+            let manifest = HakoManifest::parse("[hako]\nname = \"app\"\nversion = \"0.1.0\"\n", path).unwrap();
+
+            assert_eq!(manifest.entry, "main");
+            assert_eq!(manifest.source, PathBuf::from("app/src"));
+            assert_eq!(manifest.dependencies, Vec::new());
+            assert_eq!(manifest.options, HakoOptions::default());
+        }
+
+        it "reports all invalid values" {
+            let manifest = HakoManifest::parse(
+                concat!(
+                    "[hako]\n",
+                    "name = \"fn\"\n",
+                    "version = \"1.0\"\n",
+                    "entry = \"my-main\"\n",
+                    "\n",
+                    "[dependencies]\n",
+                    "my-util = { path = \"../util\" }\n",
+                ),
+                path,
+            );
+
+            assert_eq!(
+                manifest,
+                Err(vec![
+                    HakoManifestError::InvalidName { path: path.to_path_buf(), name: "fn".to_string() },
+                    HakoManifestError::InvalidVersion { path: path.to_path_buf(), version: "1.0".to_string() },
+                    HakoManifestError::InvalidEntry { path: path.to_path_buf(), entry: "my-main".to_string() },
+                    HakoManifestError::InvalidDependencyName { path: path.to_path_buf(), name: "my-util".to_string() },
+                ]),
+            );
+        }
+
+        it "rejects dependency on itself" {
+            let manifest = HakoManifest::parse("[hako]\nname = \"app\"\nversion = \"0.1.0\"\n[dependencies]\napp = { path = \".\" }\n", path);
+            assert_eq!(manifest, Err(vec![HakoManifestError::SelfDependency { path: path.to_path_buf(), name: "app".to_string() }]));
+        }
+
+        it "rejects unknown and missing keys" {
+            assert!(matches!(HakoManifest::parse("[hako]\nname = \"app\"\n", path), Err(ref e) if matches!(e[..], [HakoManifestError::InvalidFormat { .. }])));
+            assert!(matches!(HakoManifest::parse("[hako]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n", path), Err(ref e) if matches!(e[..], [HakoManifestError::InvalidFormat { .. }])));
+        }
+
+        it "locates format error in the file" {
+            let errors = HakoManifest::parse_in_file("[hako]\nname = \"app\"\nversion = \n", path, FileId::from(3)).unwrap_err();
+
+            match &errors[..] {
+                [HakoManifestError::InvalidFormat { span: Some(span), .. }] => {
+                    assert_eq!(span.file_id, FileId::from(3));
+                    assert_eq!(span.start.line, 2);
+                },
+                _ => panic!("unexpected errors: {:?}", errors),
+            }
+        }
+    }
+
+    describe "version" {
+        it "parses major, minor and patch" {
+            assert_eq!(HakoVersion::parse("0.10.2"), Some(HakoVersion { major: 0, minor: 10, patch: 2 }));
+            assert_eq!(HakoVersion::parse("0.10.2").unwrap().to_string(), "0.10.2");
+        }
+
+        it "rejects malformed version" {
+            assert_eq!(HakoVersion::parse("1"), None);
+            assert_eq!(HakoVersion::parse("1.0.0.0"), None);
+            assert_eq!(HakoVersion::parse("1.+0.0"), None);
+            assert_eq!(HakoVersion::parse("01.0.0"), None);
+            assert_eq!(HakoVersion::parse("1..0"), None);
+        }
+    }
+}
@@ -1,2 +1,3 @@
 mod path;
+mod resolve;
 mod type_check;
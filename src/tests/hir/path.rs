@@ -1,4 +1,6 @@
+use crate::hir::HirAccessibility;
 use crate::hir::ir::path::*;
+use crate::span::Span;
 use speculate::speculate;
 
 speculate!{
@@ -39,6 +41,7 @@ speculate!{
                 kind: HirPathKind::Hako,
                 parent: Some(0.into()),
                 children: Vec::new(),
+                span: Span::default(),
             };
 
             generate_path_tree(vec![node.clone()]);
@@ -52,6 +55,7 @@ speculate!{
                 kind: HirPathKind::Hako,
                 parent: None,
                 children: Vec::new(),
+                span: Span::default(),
             };
 
             let path_tree = generate_path_tree(vec![node.clone()]);
@@ -64,6 +68,7 @@ speculate!{
                 kind: HirPathKind::Hako,
                 parent: None,
                 children: vec![1.into()],
+                span: Span::default(),
             };
 
             let subnode = HirPathNode {
@@ -71,6 +76,7 @@ speculate!{
                 kind: HirPathKind::Module { use_declarations: Vec::new() },
                 parent: Some(0.into()),
                 children: Vec::new(),
+                span: Span::default(),
             };
 
             let path_tree = generate_path_tree(vec![hako_node, subnode.clone()]);
@@ -83,6 +89,7 @@ speculate!{
                 kind: HirPathKind::Hako,
                 parent: None,
                 children: Vec::new(),
+                span: Span::default(),
             };
 
             let path_tree = generate_path_tree(vec![node.clone()]);
@@ -95,10 +102,168 @@ speculate!{
                 kind: HirPathKind::Hako,
                 parent: None,
                 children: Vec::new(),
+                span: Span::default(),
             };
 
             let path_tree = generate_path_tree(vec![node.clone()]);
             assert_eq!(path_tree.find_child(&vec![0.into()], &"node".into()), Some((&0.into(), &node)));
         }
+
+        it "finds hako of node" {
+            let hako_node = HirPathNode {
+                id: "hako".into(),
+                kind: HirPathKind::Hako,
+                parent: None,
+                children: vec![1.into()],
+                span: Span::default(),
+            };
+
+            let subnode = HirPathNode {
+                id: "subnode".into(),
+                kind: HirPathKind::Module { use_declarations: Vec::new() },
+                parent: Some(0.into()),
+                children: Vec::new(),
+                span: Span::default(),
+            };
+
+            let path_tree = generate_path_tree(vec![hako_node, subnode]);
+            assert_eq!(path_tree.hako_of(&1.into()), Some(0.into()));
+            assert_eq!(path_tree.hako_of(&2.into()), None);
+        }
+    }
+
+    describe "relative path" {
+        // h
+        // `-- a
+        //     |-- b
+        //     |   `-- f
+        //     `-- g
+        before {
+            #[allow(unused_variables)]
+            let relative_path_tree = generate_path_tree(vec![
+                HirPathNode { id: "h".into(), kind: HirPathKind::Hako, parent: None, children: vec![1.into()], span: Span::default() },
+                HirPathNode { id: "a".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(0.into()), children: vec![2.into(), 4.into()], span: Span::default() },
+                HirPathNode { id: "b".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(1.into()), children: vec![3.into()], span: Span::default() },
+                HirPathNode { id: "f".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::Public }, parent: Some(2.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "g".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::Public }, parent: Some(1.into()), children: Vec::new(), span: Span::default() },
+            ]);
+        }
+
+        it "resolves hako from the hako root" {
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["hako".into(), "a".into(), "g".into()]), Some(4.into()));
+        }
+
+        it "resolves self from the module" {
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["self".into(), "f".into()]), Some(3.into()));
+        }
+
+        it "resolves super from the parent" {
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["super".into(), "g".into()]), Some(4.into()));
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["self".into(), "super".into(), "super".into(), "a".into()]), Some(1.into()));
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["super".into(), "super".into(), "super".into()]), None);
+        }
+
+        it "does not resolve path which is not relative" {
+            assert!(!HirPathTree::is_relative(&vec!["h".into(), "a".into()]));
+            assert_eq!(relative_path_tree.find_relative(&2.into(), &vec!["h".into(), "a".into()]), None);
+        }
+    }
+
+    describe "path query" {
+        // h
+        // |-- a
+        // |   |-- b
+        // |   |   `-- f
+        // |   `-- g
+        // `-- c
+        before {
+            #[allow(unused_variables)]
+            let query_path_tree = generate_path_tree(vec![
+                HirPathNode { id: "h".into(), kind: HirPathKind::Hako, parent: None, children: vec![1.into(), 5.into()], span: Span::default() },
+                HirPathNode { id: "a".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(0.into()), children: vec![2.into(), 4.into()], span: Span::default() },
+                HirPathNode { id: "b".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(1.into()), children: vec![3.into()], span: Span::default() },
+                HirPathNode { id: "f".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::Public }, parent: Some(2.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "g".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::Private }, parent: Some(1.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "c".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(0.into()), children: Vec::new(), span: Span::default() },
+            ]);
+        }
+
+        it "joins ids from the hako" {
+            assert_eq!(query_path_tree.path_string(&3.into()), Some("h::a::b::f".to_string()));
+            assert_eq!(query_path_tree.path_string(&0.into()), Some("h".to_string()));
+            assert_eq!(query_path_tree.path_string(&100.into()), None);
+        }
+
+        it "iterates descendants in depth-first order" {
+            assert_eq!(query_path_tree.descendants(&0.into()), vec![1.into(), 2.into(), 3.into(), 4.into(), 5.into()]);
+            assert_eq!(query_path_tree.descendants(&3.into()), Vec::<HirPathIndex>::new());
+        }
+
+        it "lists nodes of a kind" {
+            assert_eq!(query_path_tree.filter_by_kind(|v| matches!(v, HirPathKind::Function { .. })), vec![3.into(), 4.into()]);
+            assert_eq!(query_path_tree.filter_by_kind(|v| matches!(v, HirPathKind::Module { .. })), vec![1.into(), 2.into(), 5.into()]);
+        }
+
+        it "finds the nearest enclosing module" {
+            assert_eq!(query_path_tree.enclosing_module(&3.into()), Some(2.into()));
+            assert_eq!(query_path_tree.enclosing_module(&2.into()), Some(1.into()));
+            assert_eq!(query_path_tree.enclosing_module(&1.into()), None);
+        }
+    }
+
+    describe "accessibility" {
+        // h                      i
+        // |-- a                  `-- c
+        // |   |-- b
+        // |   `-- f (private)
+        // `-- g (pub@hako)
+        before {
+            #[allow(unused_variables)]
+            let accessibility_path_tree = generate_path_tree(vec![
+                HirPathNode { id: "h".into(), kind: HirPathKind::Hako, parent: None, children: vec![1.into(), 4.into()], span: Span::default() },
+                HirPathNode { id: "a".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(0.into()), children: vec![2.into(), 3.into()], span: Span::default() },
+                HirPathNode { id: "b".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(1.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "f".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::Private }, parent: Some(1.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "g".into(), kind: HirPathKind::Function { accessibility: HirAccessibility::PublicInHako }, parent: Some(0.into()), children: Vec::new(), span: Span::default() },
+                HirPathNode { id: "i".into(), kind: HirPathKind::Hako, parent: None, children: vec![6.into()], span: Span::default() },
+                HirPathNode { id: "c".into(), kind: HirPathKind::Module { use_declarations: Vec::new() }, parent: Some(5.into()), children: Vec::new(), span: Span::default() },
+            ]);
+        }
+
+        it "allows private item only in its module subtree" {
+            assert!(accessibility_path_tree.is_accessible(&1.into(), &3.into()));
+            assert!(accessibility_path_tree.is_accessible(&2.into(), &3.into()));
+            assert!(!accessibility_path_tree.is_accessible(&0.into(), &3.into()));
+        }
+
+        it "allows pub@hako item only in the same hako" {
+            assert!(accessibility_path_tree.is_accessible(&2.into(), &4.into()));
+            assert!(!accessibility_path_tree.is_accessible(&6.into(), &4.into()));
+        }
+
+        it "allows modules everywhere" {
+            assert!(accessibility_path_tree.is_accessible(&6.into(), &2.into()));
+        }
+    }
+
+    describe "dependency" {
+        it "finds nodes only in dependencies" {
+            let hako_node = |id: &str| HirPathNode {
+                id: id.into(),
+                kind: HirPathKind::Hako,
+                parent: None,
+                children: Vec::new(),
+                span: Span::default(),
+            };
+
+            let mut path_tree = generate_path_tree(vec![hako_node("a"), hako_node("b"), hako_node("c")]);
+            path_tree.add_dependency(0.into(), 1.into());
+
+            assert_eq!(path_tree.find_from(&0.into(), &vec!["a".into()]).map(|v| *v.0), Some(0.into()));
+            assert_eq!(path_tree.find_from(&0.into(), &vec!["b".into()]).map(|v| *v.0), Some(1.into()));
+            assert_eq!(path_tree.find_from(&0.into(), &vec!["c".into()]), None);
+            // Dependencies are not symmetric.
+            assert_eq!(path_tree.find_from(&1.into(), &vec!["a".into()]), None);
+        }
     }
 }
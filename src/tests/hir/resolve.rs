@@ -0,0 +1,143 @@
+use crate::hir::{HirAccessibility, HirIdentifier};
+use crate::hir::ir::path::*;
+use crate::hir::resolve::*;
+use crate::span::Span;
+use speculate::speculate;
+
+fn node(id: &str, kind: HirPathKind, parent: Option<usize>, children: Vec<usize>) -> HirPathNode {
+    HirPathNode {
+        id: id.into(),
+        kind,
+        parent: parent.map(|v| v.into()),
+        children: children.into_iter().map(|v| v.into()).collect(),
+        span: Span::default(),
+    }
+}
+
+// Path ending with `*` is a glob import.
+fn use_declaration(path: Vec<&str>) -> HirUseDeclaration {
+    let (kind, segments) = match path.split_last() {
+        Some((&"*", segments)) => (HirUseKind::Glob, segments),
+        Some((last, _)) => (HirUseKind::Named(HirIdentifier::from(*last)), &path[..]),
+        None => unreachable!(),
+    };
+
+    HirUseDeclaration::new(HirPath::Unresolved(segments.iter().map(|v| (*v).into()).collect()), kind, Span::default())
+}
+
+fn module(id: &str, parent: usize, children: Vec<usize>, use_declarations: Vec<Vec<&str>>) -> HirPathNode {
+    let use_declarations = use_declarations.into_iter().map(use_declaration).collect();
+    node(id, HirPathKind::Module { use_declarations }, Some(parent), children)
+}
+
+// Hako `h` has module `a` which defines `f` and `g`, module `b` which imports them and module `c` which defines another `f`.
+fn generate_path_tree(use_declarations: Vec<Vec<&str>>) -> HirPathTree {
+    let mut path_tree = HirPathTree::new();
+    let mut index_generator = HirPathIndexGenerator::new();
+
+    let nodes = vec![
+        node("h", HirPathKind::Hako, None, vec![1, 2, 3]),
+        module("a", 0, vec![4, 5], Vec::new()),
+        module("b", 0, Vec::new(), use_declarations),
+        module("c", 0, vec![6], Vec::new()),
+        node("f", HirPathKind::Function { accessibility: HirAccessibility::Public }, Some(1), Vec::new()),
+        node("g", HirPathKind::Function { accessibility: HirAccessibility::Public }, Some(1), Vec::new()),
+        node("f", HirPathKind::Function { accessibility: HirAccessibility::Public }, Some(3), Vec::new()),
+    ];
+
+    for each_node in nodes {
+        path_tree.add_node(&mut index_generator, None, each_node);
+    }
+
+    path_tree
+}
+
+fn use_paths(path_tree: &HirPathTree, module: usize) -> Vec<HirPath> {
+    match &path_tree.get(&module.into()).unwrap().kind {
+        HirPathKind::Module { use_declarations } => use_declarations.iter().map(|v| v.path.clone()).collect(),
+        _ => unreachable!(),
+    }
+}
+
+speculate!{
+    describe "use resolver" {
+        it "resolves use declarations and binds the last segment" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "f"]]);
+
+            assert_eq!(UseResolver::resolve(&mut path_tree), Vec::new());
+            assert_eq!(use_paths(&path_tree, 2), vec![HirPath::Resolved(4.into())]);
+            assert_eq!(path_tree.find_import(&2.into(), &"f".into()), Some((0, 4.into())));
+            assert_eq!(path_tree.find_import(&2.into(), &"g".into()), None);
+        }
+
+        it "reports unresolved import" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "unknown"]]);
+
+            assert_eq!(
+                UseResolver::resolve(&mut path_tree),
+                vec![UseResolverError::UnresolvedImport { path: "h::a::unknown".to_string(), span: Span::default() }],
+            );
+        }
+
+        it "reports ambiguous import and keeps the first one" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "f"], vec!["h", "c", "f"]]);
+
+            assert_eq!(
+                UseResolver::resolve(&mut path_tree),
+                vec![UseResolverError::AmbiguousImport { name: "f".to_string(), span: Span::default() }],
+            );
+            assert_eq!(path_tree.find_import(&2.into(), &"f".into()), Some((0, 4.into())));
+        }
+
+        it "binds alias instead of the last segment" {
+            let mut path_tree = generate_path_tree(Vec::new());
+            let mut declaration = use_declaration(vec!["h", "c", "f"]);
+            declaration.kind = HirUseKind::Named("cf".into());
+            path_tree.get_mut(&2.into()).unwrap().kind = HirPathKind::Module { use_declarations: vec![use_declaration(vec!["h", "a", "f"]), declaration] };
+
+            assert_eq!(UseResolver::resolve(&mut path_tree), Vec::new());
+            assert_eq!(path_tree.find_import(&2.into(), &"f".into()), Some((0, 4.into())));
+            assert_eq!(path_tree.find_import(&2.into(), &"cf".into()), Some((1, 6.into())));
+        }
+
+        it "imports children by glob" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "*"], vec!["h", "c", "f"]]);
+
+            assert_eq!(UseResolver::resolve(&mut path_tree), Vec::new());
+            assert_eq!(path_tree.find_import(&2.into(), &"g".into()), Some((0, 5.into())));
+            // Named import comes before glob import.
+            assert_eq!(path_tree.find_import(&2.into(), &"f".into()), Some((1, 6.into())));
+        }
+
+        it "leaves import conflicting with item in module unresolved" {
+            let mut path_tree = generate_path_tree(Vec::new());
+            path_tree.get_mut(&1.into()).unwrap().kind = HirPathKind::Module { use_declarations: vec![use_declaration(vec!["h", "c", "f"])] };
+
+            assert_eq!(UseResolver::resolve(&mut path_tree), Vec::new());
+            assert_eq!(use_paths(&path_tree, 1), vec![HirPath::Unresolved(vec!["h".into(), "c".into(), "f".into()])]);
+            assert_eq!(path_tree.find_in_scope(&1.into(), &"f".into()), Some(4.into()));
+        }
+    }
+
+    describe "accessibility" {
+        it "reports import of private item outside of its module" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "g"]]);
+            path_tree.get_mut(&5.into()).unwrap().kind = HirPathKind::Function { accessibility: HirAccessibility::Private };
+
+            assert_eq!(
+                UseResolver::resolve(&mut path_tree),
+                vec![UseResolverError::PrivateItemAccess { name: "h::a::g".to_string(), declaration: Span::default(), span: Span::default() }],
+            );
+            assert_eq!(use_paths(&path_tree, 2), vec![HirPath::Unresolved(vec!["h".into(), "a".into(), "g".into()])]);
+        }
+
+        it "does not import private item by glob" {
+            let mut path_tree = generate_path_tree(vec![vec!["h", "a", "*"]]);
+            path_tree.get_mut(&5.into()).unwrap().kind = HirPathKind::Function { accessibility: HirAccessibility::Private };
+
+            assert_eq!(UseResolver::resolve(&mut path_tree), Vec::new());
+            assert_eq!(path_tree.find_import(&2.into(), &"f".into()), Some((0, 4.into())));
+            assert_eq!(path_tree.find_import(&2.into(), &"g".into()), None);
+        }
+    }
+}
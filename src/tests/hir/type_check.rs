@@ -1,5 +1,6 @@
 use crate::hir::{*, type_check::*};
 use crate::hir::ir::{expr::*, item::*, path::*};
+use crate::span::Span;
 use speculate::speculate;
 
 fn check<F: FnMut(&mut DataTypeChecker)>(
@@ -21,48 +22,269 @@ fn check<F: FnMut(&mut DataTypeChecker)>(
 speculate!{
     describe "item" {
         it "checks path expression in function" {
-            let mut item = HirItem::Function(
+            let mut item = HirItem::from(HirItemKind::Function(
                 HirFunction {
                     accessibility: HirAccessibility::Private,
                     return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
                     arguments: Vec::new(),
                     expressions: vec![
-                        HirExpression::Operation(
+                        HirExpression::from(HirExpressionKind::Operation(
                             Box::new(
                                 HirOperation::Path(
                                     HirPath::Unresolved(vec!["unknown".into()]),
                                 ),
                             ),
-                        ),
+                        )),
                     ],
                 },
-            );
+            ));
 
             let errors = check(
                 Vec::new(),
                 |checker| checker.item(&mut item),
             );
 
-            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier]);
+            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier { span: Span::default() }]);
         }
     }
 
     describe "expression" {
         it "checks path expression" {
-            let mut expr = HirExpression::Operation(
+            let mut expr = HirExpression::from(HirExpressionKind::Operation(
                 Box::new(
                     HirOperation::Path(
                         HirPath::Unresolved(vec!["unknown".into()]),
                     ),
                 ),
+            ));
+
+            let errors = check(
+                Vec::new(),
+                |checker| checker.expression(&mut expr),
             );
 
+            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier { span: Span::default() }]);
+        }
+
+        it "does not resolve member name of member access" {
+            let mut expr = HirExpression::from(HirExpressionKind::Operation(
+                Box::new(
+                    HirOperation::MemberAccess(
+                        HirExpression::from(HirExpressionKind::Identifier("a".into())),
+                        HirExpression::from(HirExpressionKind::Identifier("member".into())),
+                    ),
+                ),
+            ));
+
+            let errors = check(
+                Vec::new(),
+                |checker| {
+                    checker.arguments = vec!["a".into()];
+                    checker.expression(&mut expr);
+                },
+            );
+
+            assert_eq!(errors, Vec::new());
+        }
+    }
+
+    describe "inference" {
+        it "infers comparison as boolean" {
+            let expr = HirExpression::from(HirExpressionKind::Operation(
+                Box::new(
+                    HirOperation::LessThan(
+                        HirExpression::from(HirExpressionKind::Literal(
+                            HirLiteral::Integer(
+                                HirIntegerLiteral {
+                                    data_type: None,
+                                    base: HirIntegerBase::Decimal,
+                                    value: "0".to_string(),
+                                    exponent: None,
+                                },
+                            ),
+                        )),
+                        HirExpression::from(HirExpressionKind::Literal(
+                            HirLiteral::Integer(
+                                HirIntegerLiteral {
+                                    data_type: None,
+                                    base: HirIntegerBase::Decimal,
+                                    value: "1".to_string(),
+                                    exponent: None,
+                                },
+                            ),
+                        )),
+                    ),
+                ),
+            ));
+
+            assert_eq!(DataTypeChecker::infer(&expr), Some(HirDataType::Primitive(HirPrimitiveDataType::Boolean)));
+        }
+
+        it "keeps integer data type in division" {
+            let expr = HirExpression::from(HirExpressionKind::Operation(
+                Box::new(
+                    HirOperation::Divide(
+                        HirExpression::from(HirExpressionKind::Literal(
+                            HirLiteral::Integer(
+                                HirIntegerLiteral {
+                                    data_type: Some(HirPrimitiveDataType::U8),
+                                    base: HirIntegerBase::Decimal,
+                                    value: "7".to_string(),
+                                    exponent: None,
+                                },
+                            ),
+                        )),
+                        HirExpression::from(HirExpressionKind::Literal(
+                            HirLiteral::Integer(
+                                HirIntegerLiteral {
+                                    data_type: Some(HirPrimitiveDataType::U8),
+                                    base: HirIntegerBase::Decimal,
+                                    value: "2".to_string(),
+                                    exponent: None,
+                                },
+                            ),
+                        )),
+                    ),
+                ),
+            ));
+
+            assert_eq!(DataTypeChecker::infer(&expr), Some(HirDataType::Primitive(HirPrimitiveDataType::U8)));
+        }
+
+        it "infers identifier from formal arguments" {
+            let expr = HirExpression::from(HirExpressionKind::Identifier("a".into()));
+            let arguments = vec![
+                HirIdentifierBinding::new(
+                    "a".into(),
+                    HirFormalArgument {
+                        mutability: HirMutability::Immutable,
+                        data_type: HirDataType::Primitive(HirPrimitiveDataType::S32),
+                    },
+                ),
+            ];
+
+            assert_eq!(DataTypeChecker::infer(&expr), None);
+            assert_eq!(DataTypeChecker::infer_in(&expr, &arguments), Some(HirDataType::Primitive(HirPrimitiveDataType::S32)));
+        }
+    }
+
+    describe "identifier" {
+        it "replaces imported identifier with path" {
+            let mut expr = HirExpression::from(HirExpressionKind::Identifier("f".into()));
+
+            let errors = check(
+                vec![
+                    HirPathNode {
+                        id: "h".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: vec![1.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "a".into(),
+                        kind: HirPathKind::Module { use_declarations: vec![HirUseDeclaration::new(HirPath::Resolved(2.into()), HirUseKind::Named("f".into()), Span::default())] },
+                        parent: Some(0.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "f".into(),
+                        kind: HirPathKind::Function { accessibility: HirAccessibility::Public },
+                        parent: None,
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                ],
+                |checker| {
+                    checker.current_module = Some(1.into());
+                    checker.expression(&mut expr);
+                },
+            );
+
+            assert_eq!(errors, Vec::new());
+            assert_eq!(expr.kind, HirExpressionKind::Operation(Box::new(HirOperation::Path(HirPath::Resolved(2.into())))));
+        }
+
+        it "keeps argument as identifier" {
+            let mut expr = HirExpression::from(HirExpressionKind::Identifier("x".into()));
+
+            let errors = check(
+                Vec::new(),
+                |checker| {
+                    checker.arguments = vec!["x".into()];
+                    checker.expression(&mut expr);
+                },
+            );
+
+            assert_eq!(errors, Vec::new());
+            assert_eq!(expr.kind, HirExpressionKind::Identifier("x".into()));
+        }
+
+        it "detects unknown identifier" {
+            let mut expr = HirExpression::from(HirExpressionKind::Identifier("unknown".into()));
+
             let errors = check(
                 Vec::new(),
                 |checker| checker.expression(&mut expr),
             );
 
-            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier]);
+            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier { span: Span::default() }]);
+        }
+    }
+
+    describe "unused import" {
+        it "warns import which is never referred to" {
+            let nodes = vec![
+                HirPathNode {
+                    id: "h".into(),
+                    kind: HirPathKind::Hako,
+                    parent: None,
+                    children: vec![1.into()],
+                    span: Span::default(),
+                },
+                HirPathNode {
+                    id: "a".into(),
+                    kind: HirPathKind::Module {
+                        use_declarations: vec![
+                            HirUseDeclaration::new(HirPath::Resolved(2.into()), HirUseKind::Named("f".into()), Span::default()),
+                            HirUseDeclaration::new(HirPath::Resolved(3.into()), HirUseKind::Named("g".into()), Span::default()),
+                        ],
+                    },
+                    parent: Some(0.into()),
+                    children: Vec::new(),
+                    span: Span::default(),
+                },
+                HirPathNode {
+                    id: "f".into(),
+                    kind: HirPathKind::Function { accessibility: HirAccessibility::Public },
+                    parent: None,
+                    children: Vec::new(),
+                    span: Span::default(),
+                },
+                HirPathNode {
+                    id: "g".into(),
+                    kind: HirPathKind::Function { accessibility: HirAccessibility::Public },
+                    parent: None,
+                    children: Vec::new(),
+                    span: Span::default(),
+                },
+            ];
+
+            let mut path_tree = HirPathTree::new();
+            let mut index_generator = HirPathIndexGenerator::new();
+
+            for each_node in nodes {
+                path_tree.add_node(&mut index_generator, None, each_node);
+            }
+
+            let mut checker = DataTypeChecker::new(&path_tree);
+            checker.current_module = Some(1.into());
+            checker.expression(&mut HirExpression::from(HirExpressionKind::Identifier("f".into())));
+            checker.unused_imports();
+
+            assert_eq!(checker.errors, Vec::new());
+            assert_eq!(checker.warnings, vec![DataTypeWarning::UnusedImport { name: "g".to_string(), span: Span::default() }]);
         }
     }
 
@@ -77,6 +299,7 @@ speculate!{
                         kind: HirPathKind::Hako,
                         parent: None,
                         children: Vec::new(),
+                        span: Span::default(),
                     },
                 ],
                 |checker| checker.path(&mut path),
@@ -94,7 +317,175 @@ speculate!{
                 |checker| checker.path(&mut path),
             );
 
-            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier]);
+            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier { span: Span::default() }]);
+        }
+
+        it "resolves path from imported name" {
+            let mut path = HirPath::Unresolved(vec!["b".into(), "f".into()]);
+
+            let errors = check(
+                vec![
+                    HirPathNode {
+                        id: "h".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: vec![1.into(), 2.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "a".into(),
+                        kind: HirPathKind::Module { use_declarations: vec![HirUseDeclaration::new(HirPath::Resolved(2.into()), HirUseKind::Named("b".into()), Span::default())] },
+                        parent: Some(0.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "b".into(),
+                        kind: HirPathKind::Module { use_declarations: Vec::new() },
+                        parent: Some(0.into()),
+                        children: vec![3.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "f".into(),
+                        kind: HirPathKind::Function { accessibility: HirAccessibility::Public },
+                        parent: Some(2.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                ],
+                |checker| {
+                    checker.current_module = Some(1.into());
+                    checker.path(&mut path);
+                },
+            );
+
+            assert_eq!(errors, Vec::new());
+            assert_eq!(path, HirPath::Resolved(3.into()));
+        }
+
+        it "detects path to private item in another module" {
+            let mut path = HirPath::Unresolved(vec!["hako".into(), "b".into(), "f".into()]);
+
+            let errors = check(
+                vec![
+                    HirPathNode {
+                        id: "h".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: vec![1.into(), 2.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "a".into(),
+                        kind: HirPathKind::Module { use_declarations: Vec::new() },
+                        parent: Some(0.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "b".into(),
+                        kind: HirPathKind::Module { use_declarations: Vec::new() },
+                        parent: Some(0.into()),
+                        children: vec![3.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "f".into(),
+                        kind: HirPathKind::Function { accessibility: HirAccessibility::Private },
+                        parent: Some(2.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                ],
+                |checker| {
+                    checker.current_module = Some(1.into());
+                    checker.path(&mut path);
+                },
+            );
+
+            assert_eq!(errors, vec![DataTypeError::PrivateItemAccess { name: "h::b::f".to_string(), declaration: Span::default(), span: Span::default() }]);
+            assert!(matches!(path, HirPath::Unresolved(_)));
+        }
+
+        it "detects path into hako which is not a dependency" {
+            let mut path = HirPath::Unresolved(vec!["other".into()]);
+
+            let errors = check(
+                vec![
+                    HirPathNode {
+                        id: "current".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "other".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                ],
+                |checker| {
+                    checker.current_hako = Some(0.into());
+                    checker.path(&mut path);
+                },
+            );
+
+            assert_eq!(errors, vec![DataTypeError::UndeclaredDependency { hako: "other".to_string(), span: Span::default() }]);
+        }
+
+        it "does not report dependency when local module shadows hako" {
+            let mut path = HirPath::Unresolved(vec!["other".into(), "f".into()]);
+
+            let errors = check(
+                vec![
+                    HirPathNode {
+                        id: "current".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: vec![2.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "other".into(),
+                        kind: HirPathKind::Hako,
+                        parent: None,
+                        children: vec![4.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "main".into(),
+                        kind: HirPathKind::Module { use_declarations: Vec::new() },
+                        parent: Some(0.into()),
+                        children: vec![3.into()],
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "other".into(),
+                        kind: HirPathKind::Module { use_declarations: Vec::new() },
+                        parent: Some(2.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                    HirPathNode {
+                        id: "f".into(),
+                        kind: HirPathKind::Function { accessibility: HirAccessibility::Public },
+                        parent: Some(1.into()),
+                        children: Vec::new(),
+                        span: Span::default(),
+                    },
+                ],
+                |checker| {
+                    checker.current_hako = Some(0.into());
+                    checker.current_module = Some(2.into());
+                    checker.path(&mut path);
+                },
+            );
+
+            assert_eq!(errors, vec![DataTypeError::UnknownIdentifier { span: Span::default() }]);
         }
     }
 }
@@ -4,11 +4,129 @@ mod jsify;
 use speculate::speculate;
 
 use crate::*;
-use crate::js::{JsTranspiler, JsTranspilerOptions};
+use crate::js::{JsTranspiler, JsTranspilerError, JsTranspilerOptions, JsTranspilerOutput, JsTranspilerWarning};
+use crate::hir::hirify::TreeHirifierError;
+use crate::hir::resolve::UseResolverError;
+use crate::hir::type_check::DataTypeWarning;
+use crate::ast::operator::OperationParserError;
+use crate::span::{FileId, SourceMap, SourcePosition, Span};
 
 speculate!{
+    before {
+        let compiler = JsTranspiler::new(JsTranspilerOptions::default());
+
+        // Positions are triples of index, line and column in the source.
+        #[allow(unused)]
+        let span = |start: (usize, usize, usize), end: (usize, usize, usize)| Span::new(
+            FileId::default(),
+            SourcePosition::new(start.0, start.1, start.2),
+            SourcePosition::new(end.0, end.1, end.2),
+        );
+
+        // Source of `compile()` is registered as the entry file.
+        #[allow(unused)]
+        let source_map = |input: &str| {
+            let mut source_map = SourceMap::new();
+            source_map.add("main.kr".to_string(), input.to_string());
+            source_map
+        };
+    }
+
     it "" {
-        let compiler = JsTranspiler::new(JsTranspilerOptions);
-        assert_eq!(compiler.compile("fn main(){}"), Ok("function i_2(){}".to_string()));
+        assert_eq!(
+            compiler.compile("fn main(){}"),
+            JsTranspilerOutput {
+                code: Some("function i_2(){}".to_string()),
+                errors: Vec::new(),
+                warnings: Vec::new(),
+                source_map: source_map("fn main(){}"),
+            },
+        );
+    }
+
+    it "reports multiple syntax errors without code" {
+        assert_eq!(
+            compiler.compile("fn\nfn main() {0 +}"),
+            JsTranspilerOutput {
+                code: None,
+                errors: vec![
+                    JsTranspilerError::HirifierError(TreeHirifierError::InvalidSyntax { name: "invalid_item".to_string(), source: "fn".to_string(), span: span((0, 0, 0), (2, 0, 2)) }),
+                    JsTranspilerError::HirifierError(TreeHirifierError::InvalidSyntax { name: "invalid_function_body".to_string(), source: "0 +".to_string(), span: span((14, 1, 11), (17, 1, 14)) }),
+                ],
+                warnings: Vec::new(),
+                source_map: source_map("fn\nfn main() {0 +}"),
+            },
+        );
+    }
+
+    it "stops before code generation on hirifier error" {
+        let output = compiler.compile("fn main(){0 < 1 < 2}");
+
+        assert_eq!(
+            output,
+            JsTranspilerOutput {
+                code: None,
+                errors: vec![
+                    JsTranspilerError::HirifierError(
+                        TreeHirifierError::InvalidOperation {
+                            error: OperationParserError::NonAssociativeChain { token_index: 3 },
+                            span: span((16, 0, 16), (17, 0, 17)),
+                        },
+                    ),
+                ],
+                warnings: Vec::new(),
+                source_map: source_map("fn main(){0 < 1 < 2}"),
+            },
+        );
+
+        assert!(!output.is_success());
+        assert_eq!(output.diagnostics().len(), 1);
+    }
+
+    it "reports unresolved import" {
+        assert_eq!(
+            compiler.compile("use main::main::unknown\nfn main(){}"),
+            JsTranspilerOutput {
+                code: None,
+                errors: vec![
+                    JsTranspilerError::ResolverError(UseResolverError::UnresolvedImport { path: "main::main::unknown".to_string(), span: span((4, 0, 4), (23, 0, 23)) }),
+                ],
+                warnings: Vec::new(),
+                source_map: source_map("use main::main::unknown\nfn main(){}"),
+            },
+        );
+    }
+
+    it "generates code with unused import warning" {
+        assert_eq!(
+            compiler.compile("use main::main as m\nfn f(){}"),
+            JsTranspilerOutput {
+                code: Some("function i_3(){}".to_string()),
+                errors: Vec::new(),
+                warnings: vec![
+                    JsTranspilerWarning::DataTypeWarning(DataTypeWarning::UnusedImport { name: "m".to_string(), span: span((4, 0, 4), (19, 0, 19)) }),
+                ],
+                source_map: source_map("use main::main as m\nfn f(){}"),
+            },
+        );
+    }
+
+    it "does not generate duplicate definition" {
+        let hako = JsTranspiler::source_hako(compiler.parse("fn f(){}\nfn f(){}").unwrap(), FileId::default());
+        let (hir, errors, _) = compiler.analyze_hakos(vec![&hako.ast()]);
+
+        assert_eq!(
+            errors,
+            vec![
+                JsTranspilerError::HirifierError(TreeHirifierError::DuplicateDefinition { name: "f".to_string(), previous: span((3, 0, 3), (4, 0, 4)), span: span((12, 1, 3), (13, 1, 4)) }),
+            ],
+        );
+        assert_eq!(compiler.generate(&hir), "function i_2(){}".to_string());
+    }
+
+    it "emits diagnostics as JSON on request" {
+        let json: serde_json::Value = serde_json::from_str(&compiler.compile("fn").diagnostics_json()).unwrap();
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+        assert_eq!(json["diagnostics"][0]["file"], "main.kr");
     }
 }
@@ -75,6 +75,15 @@ speculate!{
         }
     }
 
+    describe "runtime helper" {
+        it "throws on zero divisor" {
+            assert_eq!(
+                JsCodeGenerator::item(&JsItem::RuntimeHelper(JsRuntimeHelper::CheckDivisor)),
+                "function k_check_divisor(v){if(v==0)throw new RangeError(\"division by zero\");return v}".to_string(),
+            );
+        }
+    }
+
     describe "statement" {
         describe "expression" {
             it "literal" {
@@ -102,4 +111,31 @@ speculate!{
             }
         }
     }
+
+    describe "operation" {
+        it "puts prefix symbol before term" {
+            let operation = JsOperation::Prefix(
+                "!",
+                JsExpression::Identifier("a".to_string()),
+            );
+
+            assert_eq!(
+                JsCodeGenerator::operation(&operation),
+                "!a".to_string(),
+            );
+        }
+
+        it "puts infix symbol between terms" {
+            let operation = JsOperation::Infix(
+                "+=",
+                JsExpression::Identifier("a".to_string()),
+                JsExpression::Identifier("b".to_string()),
+            );
+
+            assert_eq!(
+                JsCodeGenerator::operation(&operation),
+                "a+=b".to_string(),
+            );
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
+use crate::js::code::JsCodeGenerator;
 use crate::js::jsify::JsGenerator;
 use crate::js::ir::{item::*, stmt::*};
 use crate::hir::*;
 use crate::hir::ir::{expr::*, item::*, path::*};
+use crate::span::Span;
 use speculate::speculate;
 
 speculate!{
@@ -11,20 +13,22 @@ speculate!{
             it "reflects identifier, arguments and statements" {
                 let path_tree = HirPathTree {
                     hako_indexes: Vec::new(),
+                    dependencies: BTreeMap::new(),
                     nodes: BTreeMap::from([(
                         HirPathIndex::from(0),
                         HirPathNode {
                             id: "f".into(),
-                            kind: HirPathKind::Function,
+                            kind: HirPathKind::Function { accessibility: HirAccessibility::Private },
                             parent: None,
                             children: Vec::new(),
+                            span: Span::default(),
                         },
                     )]),
                 };
 
                 let mut generator = JsGenerator::new(&path_tree);
 
-                let item = HirItem::Function(
+                let item = HirItem::from(HirItemKind::Function(
                     HirFunction {
                         accessibility: HirAccessibility::Private,
                         return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
@@ -38,12 +42,12 @@ speculate!{
                             ),
                         ],
                         expressions: vec![
-                            HirExpression::Literal(
+                            HirExpression::from(HirExpressionKind::Literal(
                                 HirLiteral::Boolean(true),
-                            ),
+                            )),
                         ],
                     },
-                );
+                ));
 
                 assert_eq!(
                     generator.item(&HirPathIndexBinding::new(0.into(), item)),
@@ -74,9 +78,9 @@ speculate!{
 
             assert_eq!(
                 generator.statement(
-                    &HirExpression::Literal(
+                    &HirExpression::from(HirExpressionKind::Literal(
                         HirLiteral::Boolean(true),
-                    ),
+                    )),
                 ),
                 JsStatement::Expression(
                     JsExpression::Literal(
@@ -96,6 +100,253 @@ speculate!{
                     JsLiteral::Boolean(true),
                 );
             }
+
+            it "represents 64-bit integer as bigint" {
+                let path_tree = HirPathTree::new();
+                let mut generator = JsGenerator::new(&path_tree);
+
+                assert_eq!(
+                    generator.literal(
+                        &HirLiteral::Integer(
+                            HirIntegerLiteral {
+                                data_type: Some(HirPrimitiveDataType::S64),
+                                base: HirIntegerBase::Decimal,
+                                value: "1".to_string(),
+                                exponent: None,
+                            },
+                        ),
+                    ),
+                    JsLiteral::BigInt("1".to_string()),
+                );
+            }
+        }
+    }
+
+    describe "operation" {
+        before {
+            #[allow(unused)]
+            let integer = |value: &str, data_type: Option<HirPrimitiveDataType>|
+                HirExpression::from(HirExpressionKind::Literal(
+                    HirLiteral::Integer(
+                        HirIntegerLiteral {
+                            data_type,
+                            base: HirIntegerBase::Decimal,
+                            value: value.to_string(),
+                            exponent: None,
+                        },
+                    ),
+                ));
+
+            #[allow(unused)]
+            let js_integer = |value: &str|
+                JsExpression::Literal(
+                    JsLiteral::Integer(value.to_string()),
+                );
+
+            #[allow(unused)]
+            let js_operation = |operation: JsOperation|
+                JsExpression::Operation(Box::new(operation));
+        }
+
+        it "takes symbol from operator table" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            assert_eq!(
+                generator.operation(&HirOperation::Equal(integer("1", None), integer("2", None))),
+                js_operation(JsOperation::Infix("===", js_integer("1"), js_integer("2"))),
+            );
+
+            assert_eq!(
+                generator.operation(&HirOperation::BitNot(integer("1", None))),
+                js_operation(JsOperation::Prefix("~", js_integer("1"))),
+            );
+        }
+
+        it "groups bitwise operation inside comparison" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+            let bit_and = HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::BitAnd(integer("1", None), integer("2", None)))));
+            let js = generator.operation(&HirOperation::Equal(bit_and, integer("3", None)));
+
+            assert_eq!(JsCodeGenerator::expression(&js), "(1&2)===3".to_string());
+        }
+
+        it "groups negated sum" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+            let sum = HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Add(integer("1", None), integer("2", None)))));
+            let js = generator.operation(&HirOperation::Negative(sum));
+
+            assert_eq!(JsCodeGenerator::expression(&js), "-(1+2)".to_string());
+        }
+
+        it "groups right term of left-associative operation" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+            let difference = HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Subtract(integer("2", None), integer("3", None)))));
+            let js = generator.operation(&HirOperation::Subtract(integer("1", None), difference));
+
+            assert_eq!(JsCodeGenerator::expression(&js), "1-(2-3)".to_string());
+        }
+
+        it "truncates integer quotient" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            assert_eq!(
+                generator.operation(&HirOperation::Divide(integer("7", None), integer("2", None))),
+                js_operation(
+                    JsOperation::FunctionCall(
+                        js_operation(
+                            JsOperation::Infix(
+                                ".",
+                                JsExpression::Identifier("Math".to_string()),
+                                JsExpression::Identifier("trunc".to_string()),
+                            ),
+                        ),
+                        vec![
+                            js_operation(JsOperation::Infix("/", js_integer("7"), js_integer("2"))),
+                        ],
+                    ),
+                ),
+            );
+        }
+
+        it "does not truncate bigint quotient" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            assert_eq!(
+                generator.operation(
+                    &HirOperation::Divide(
+                        integer("7", Some(HirPrimitiveDataType::U64)),
+                        integer("2", Some(HirPrimitiveDataType::U64)),
+                    ),
+                ),
+                js_operation(
+                    JsOperation::Infix(
+                        "/",
+                        JsExpression::Literal(JsLiteral::BigInt("7".to_string())),
+                        JsExpression::Literal(JsLiteral::BigInt("2".to_string())),
+                    ),
+                ),
+            );
+        }
+
+        it "truncates quotient of integer arguments" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+            let argument = |id: &str| HirIdentifierBinding::new(
+                id.into(),
+                HirFormalArgument {
+                    mutability: HirMutability::Immutable,
+                    data_type: HirDataType::Primitive(HirPrimitiveDataType::S32),
+                },
+            );
+            let identifier = |id: &str| HirExpression::from(HirExpressionKind::Identifier(id.into()));
+
+            // fn f(a s32, b s32) { a / b }
+            let item = HirItem::from(HirItemKind::Function(
+                HirFunction {
+                    accessibility: HirAccessibility::Private,
+                    return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
+                    arguments: vec![argument("a"), argument("b")],
+                    expressions: vec![
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Divide(identifier("a"), identifier("b"))))),
+                    ],
+                },
+            ));
+
+            let js = generator.item(&HirPathIndexBinding::new(0.into(), item));
+            assert_eq!(JsCodeGenerator::item(&js), "function i_0(a,b){Math.trunc(a/b)}".to_string());
+        }
+
+        it "expands integer divide substitution" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            // fn f(a s32) { a /= 2 }
+            let item = HirItem::from(HirItemKind::Function(
+                HirFunction {
+                    accessibility: HirAccessibility::Private,
+                    return_type: HirDataType::Primitive(HirPrimitiveDataType::None),
+                    arguments: vec![
+                        HirIdentifierBinding::new(
+                            "a".into(),
+                            HirFormalArgument {
+                                mutability: HirMutability::Mutable,
+                                data_type: HirDataType::Primitive(HirPrimitiveDataType::S32),
+                            },
+                        ),
+                    ],
+                    expressions: vec![
+                        HirExpression::from(HirExpressionKind::Operation(Box::new(
+                            HirOperation::DivideSubstitute(HirExpression::from(HirExpressionKind::Identifier("a".into())), integer("2", None)),
+                        ))),
+                    ],
+                },
+            ));
+
+            let js = generator.item(&HirPathIndexBinding::new(0.into(), item));
+            assert_eq!(JsCodeGenerator::item(&js), "function i_0(a){a=Math.trunc(a/2)}".to_string());
+        }
+
+        it "converts integer term into bigint" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            let js = generator.operation(&HirOperation::Divide(integer("1", Some(HirPrimitiveDataType::S64)), integer("2", None)));
+            assert_eq!(JsCodeGenerator::expression(&js), "1n/2n".to_string());
+
+            let negative = HirExpression::from(HirExpressionKind::Operation(Box::new(HirOperation::Negative(integer("2", None)))));
+            let js = generator.operation(&HirOperation::Add(negative, integer("1", Some(HirPrimitiveDataType::U64))));
+            assert_eq!(JsCodeGenerator::expression(&js), "BigInt(-2)+1n".to_string());
+        }
+
+        it "checks divisor in checked mode" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+            generator.checked_arithmetic = true;
+
+            assert_eq!(
+                generator.operation(&HirOperation::Remainder(integer("7", None), integer("0", None))),
+                js_operation(
+                    JsOperation::Infix(
+                        "%",
+                        js_integer("7"),
+                        js_operation(
+                            JsOperation::FunctionCall(
+                                JsExpression::Identifier("k_check_divisor".to_string()),
+                                vec![js_integer("0")],
+                            ),
+                        ),
+                    ),
+                ),
+            );
+
+            assert_eq!(generator.runtime_helpers, vec![JsRuntimeHelper::CheckDivisor]);
+        }
+
+        it "converts non-boolean logical terms into boolean" {
+            let path_tree = HirPathTree::new();
+            let mut generator = JsGenerator::new(&path_tree);
+
+            assert_eq!(
+                generator.operation(
+                    &HirOperation::And(
+                        HirExpression::from(HirExpressionKind::Literal(HirLiteral::Boolean(true))),
+                        integer("1", None),
+                    ),
+                ),
+                js_operation(
+                    JsOperation::Infix(
+                        "&&",
+                        JsExpression::Literal(JsLiteral::Boolean(true)),
+                        js_operation(JsOperation::Prefix("!", js_operation(JsOperation::Prefix("!", js_integer("1"))))),
+                    ),
+                ),
+            );
         }
     }
 }
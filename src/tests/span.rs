@@ -0,0 +1,40 @@
+use crate::span::*;
+use speculate::speculate;
+
+speculate!{
+    before {
+        #[allow(unused)]
+        let new_span = |start: usize, end: usize| Span::new(
+            FileId::default(),
+            SourcePosition::new(start, 0, start),
+            SourcePosition::new(end, 0, end),
+        );
+    }
+
+    describe "span" {
+        it "compares location in equality" {
+            assert_eq!(new_span(0, 1), new_span(0, 1));
+            assert_ne!(new_span(0, 1), new_span(2, 3));
+        }
+
+        it "joins two spans" {
+            assert_eq!(new_span(4, 6).join(&new_span(0, 2)), new_span(0, 6));
+            assert_eq!(new_span(0, 6).join(&new_span(2, 4)), new_span(0, 6));
+        }
+
+        it "measures length" {
+            assert_eq!(new_span(2, 5).len(), 3);
+            assert!(new_span(2, 2).is_empty());
+        }
+
+        it "converts byte index to character position" {
+            assert_eq!(SourcePosition::from_byte_index("a\nあb", 5), SourcePosition::new(3, 1, 1));
+            assert_eq!(SourcePosition::from_byte_index("ab", 10), SourcePosition::new(2, 0, 2));
+        }
+
+        it "advances position by text" {
+            assert_eq!(SourcePosition::new(1, 0, 1).advance_by("ab"), SourcePosition::new(3, 0, 3));
+            assert_eq!(SourcePosition::new(1, 0, 1).advance_by("a\nあb"), SourcePosition::new(5, 1, 2));
+        }
+    }
+}